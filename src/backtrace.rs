@@ -6,13 +6,27 @@ use std::fmt::Result;
 
 use crate::config::Bundle;
 use crate::consts::UNKNOWN;
+use crate::link;
 use crate::location::Location;
+use crate::Verbosity;
 
+#[cfg(feature = "anyhow")]
+pub(crate) use capture::parse_std;
+pub(crate) use capture::Capture;
 pub use filter::FrameFilter;
+#[cfg(feature = "cli")]
+pub(crate) use parse::frame as parse_frame;
+#[cfg(feature = "cli")]
+pub(crate) use parse::location as parse_location;
+pub use parse::BacktraceError;
+pub use parse::ParseDiagnostic;
+pub use parse::ParsedBacktrace;
 
+mod capture;
 mod convert;
 mod display;
 mod filter;
+mod parse;
 
 /// Type to smuggle mutable vector to display impl and enable trait impls. One-time use.
 #[derive(Default)]
@@ -20,6 +34,12 @@ struct Backtrace<'a> {
     frames: Cell<Vec<Frame<'a>>>,
 }
 
+/// Resolves frames of backtrace captured with `backtrace` crate
+#[cfg(feature = "backtrace")]
+pub(crate) fn resolve(backtrace: &::backtrace::Backtrace) -> Vec<Frame<'_>> {
+    Backtrace::from(backtrace).frames.into_inner()
+}
+
 /// Representation of single frame in backtrace
 ///
 /// Mainly used to filter backtrace for unnecessary info.
@@ -28,6 +48,8 @@ pub struct Frame<'a> {
     index: usize,
     name: Option<Cow<'a, str>>,
     location: Option<Location<'a>>,
+    address: Option<usize>,
+    inlined: bool,
 }
 
 impl Frame<'_> {
@@ -52,6 +74,44 @@ impl Frame<'_> {
         self.location.as_ref()
     }
 
+    /// Instruction address of the frame, if known
+    ///
+    /// Only available for frames captured with `backtrace` feature.
+    #[must_use]
+    #[inline]
+    pub fn address(&self) -> Option<usize> {
+        self.address
+    }
+
+    /// Whether the frame was inlined into its caller, which shares the same index
+    #[must_use]
+    #[inline]
+    pub fn is_inlined(&self) -> bool {
+        self.inlined
+    }
+
+    /// Copies frame, borrowing its contents
+    pub(crate) fn borrowed(&self) -> Frame<'_> {
+        Frame {
+            index: self.index,
+            name: self.name.as_deref().map(Cow::Borrowed),
+            location: self.location.as_ref().map(Location::borrowed),
+            address: self.address,
+            inlined: self.inlined,
+        }
+    }
+
+    /// Converts frame into one which owns its contents
+    pub(crate) fn into_owned(self) -> Frame<'static> {
+        Frame {
+            index: self.index,
+            name: self.name.map(|name| Cow::Owned(name.into_owned())),
+            location: self.location.map(Location::into_owned),
+            address: self.address,
+            inlined: self.inlined,
+        }
+    }
+
     #[cfg(feature = "backtrace")]
     fn symbolify(&self) -> (&str, Option<&str>) {
         let Some(name) = self.name() else {
@@ -88,10 +148,45 @@ impl Display for Bundle<'_, &Frame<'_>> {
             .then_some(theme.dependency)
             .unwrap_or(theme.package)
             .style(name);
-        let hash = hash.unwrap_or("");
-        //let hash = theme.hash.style(hash);
         let location = config.bundle(frame.location());
 
-        write!(f, "{:>2}: {name}{hash}\n    at {location}", frame.index)
+        write!(f, "{:>2}: {name}", frame.index)?;
+        if let Some(hash) = hash {
+            write!(f, "{}", theme.hash.style(hash))?;
+        }
+        if frame.inlined {
+            write!(f, " {}", theme.hash.style("[inlined]"))?;
+        }
+        if let Some(address) = frame
+            .address
+            .filter(|_| config.selected_verbosity() == Verbosity::Full)
+        {
+            write!(f, " {}", theme.hash.style(format_args!("@ {address:#x}")))?;
+        }
+        write!(f, "\n    at {location}")?;
+        if let Some(url) = frame
+            .location()
+            .filter(|_| config.web_links)
+            .and_then(link::web)
+        {
+            write!(f, "\n    {}", theme.url.style(url))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn unhashed() {
+        let config = Config::blank();
+        let parsed = ParsedBacktrace::parse("   0: app::main\n").unwrap();
+        let rendered = config.bundle(&parsed.frames()[0]).to_string();
+        let empty = config.theme.hash.style("").to_string();
+        assert!(!empty.is_empty());
+        assert!(!rendered.contains(&empty));
     }
 }
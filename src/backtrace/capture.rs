@@ -0,0 +1,82 @@
+use std::sync::OnceLock;
+
+use super::Frame;
+#[cfg(any(feature = "anyhow", not(feature = "backtrace")))]
+use super::ParsedBacktrace;
+
+/// Backtrace captured along with error or panic
+///
+/// Frames are resolved at most once, when first requested, so repeated renders
+/// of the same report share them.
+pub struct Capture {
+    #[cfg(feature = "backtrace")]
+    backtrace: ::backtrace::Backtrace,
+    #[cfg(not(feature = "backtrace"))]
+    backtrace: std::backtrace::Backtrace,
+    frames: OnceLock<Vec<Frame<'static>>>,
+}
+
+impl Capture {
+    /// Captures backtrace of the current thread, regardless of environment
+    #[cfg(feature = "backtrace")]
+    pub fn new() -> Self {
+        Self {
+            backtrace: ::backtrace::Backtrace::new_unresolved(),
+            frames: OnceLock::new(),
+        }
+    }
+
+    /// Captures backtrace of the current thread, regardless of environment
+    #[cfg(not(feature = "backtrace"))]
+    pub fn new() -> Self {
+        Self {
+            backtrace: std::backtrace::Backtrace::force_capture(),
+            frames: OnceLock::new(),
+        }
+    }
+
+    /// Resolved frames of the backtrace
+    #[cfg(feature = "backtrace")]
+    pub fn frames(&self) -> &[Frame<'static>] {
+        self.frames.get_or_init(|| {
+            let mut backtrace = self.backtrace.clone();
+            backtrace.resolve();
+            super::resolve(&backtrace)
+                .into_iter()
+                .map(Frame::into_owned)
+                .collect()
+        })
+    }
+
+    /// Resolved frames of the backtrace
+    #[cfg(not(feature = "backtrace"))]
+    pub fn frames(&self) -> &[Frame<'static>] {
+        self.frames.get_or_init(|| parse_std(&self.backtrace))
+    }
+}
+
+/// Extracts frames from backtrace captured by standard library
+///
+/// Standard library provides no structured access to frames, so the textual
+/// representation has to be parsed.
+#[cfg(any(feature = "anyhow", not(feature = "backtrace")))]
+pub(crate) fn parse_std(backtrace: &std::backtrace::Backtrace) -> Vec<Frame<'static>> {
+    ParsedBacktrace::parse(&backtrace.to_string())
+        .map(ParsedBacktrace::into_frames)
+        .unwrap_or_default()
+        .into_iter()
+        .map(Frame::into_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached() {
+        let capture = Capture::new();
+        assert!(!capture.frames().is_empty());
+        assert!(std::ptr::eq(capture.frames(), capture.frames()));
+    }
+}
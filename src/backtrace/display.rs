@@ -1,3 +1,5 @@
+#[cfg(feature = "tracing")]
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -5,9 +7,14 @@ use std::fmt::Result;
 use std::fmt::Write;
 
 use crate::config::Bundle;
-use crate::verbosity::Verbosity;
+use crate::indent::Indent;
+#[cfg(feature = "tracing")]
+use crate::location::Location;
+use crate::snippet::Snippet;
+use crate::snippet::Sources;
 
-use super::convert::BacktraceParser;
+#[cfg(feature = "backtrace")]
+use super::resolve;
 use super::Backtrace;
 use super::Frame;
 
@@ -38,22 +45,18 @@ impl Display for Bundle<'_, &Backtrace<'_>> {
 
         write!(f, "{:━^80}", " BACKTRACE ")?;
         if frames.is_empty() {
-            return write!(f, "{:^80}", "<empty backtrace>");
+            return write!(f, "\n{:^80}", "<empty backtrace>");
         }
 
         let last = frames.last().map_or(0, Frame::index);
 
-        if config.selected_verbosity() != Verbosity::Full {
-            for filter in &config.filters {
-                filter(&mut frames);
-                frames.sort_unstable_by_key(Frame::index);
-            }
-        }
+        config.apply_filters(&mut frames);
 
         let buffer = RefCell::new(String::with_capacity(128));
+        let sources = Sources::default();
         let mut next = 0;
         for frame in frames {
-            let delta = frame.index - next;
+            let delta = frame.index.saturating_sub(next);
             if delta != 0 {
                 write!(
                     f,
@@ -65,6 +68,16 @@ impl Display for Bundle<'_, &Backtrace<'_>> {
                 )?;
             }
             write!(f, "\n{}", config.bundle(&frame))?;
+            if let Some(location) = frame
+                .location()
+                .filter(|_| !frame.is_dependency_code() && config.show_snippets())
+            {
+                let snippet = Snippet {
+                    location,
+                    sources: &sources,
+                };
+                write!(Indent::new(f, "      "), "{}", config.bundle(snippet))?;
+            }
             next = frame.index + 1;
         }
 
@@ -86,27 +99,59 @@ impl Display for Bundle<'_, &Backtrace<'_>> {
 #[cfg(feature = "backtrace")]
 impl Display for Bundle<'_, &backtrace::Backtrace> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let backtrace = Backtrace::from(self.data);
-        Display::fmt(&self.config().bundle(&backtrace), f)
+        let backtrace: Backtrace = resolve(self.data).into();
+        Display::fmt(&self.config.bundle(&backtrace), f)
     }
 }
 
-impl Display for Bundle<'_, &std::backtrace::Backtrace> {
+#[cfg(feature = "tracing")]
+impl Display for Bundle<'_, &tracing_error::SpanTrace> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let backtrace = self.data.to_string();
-        let backtrace: Backtrace = BacktraceParser::new(&backtrace).collect::<Vec<_>>().into();
-        Display::fmt(&self.config.bundle(&backtrace), f)
+        let config = self.config;
+        let theme = &config.theme;
+
+        write!(f, "{:━^80}", " SPANTRACE ")?;
+
+        let mut result = Ok(());
+        let mut index = 0;
+        self.data.with_spans(|metadata, fields| {
+            let location = metadata
+                .file()
+                .zip(metadata.line())
+                .map(|(file, line)| Location {
+                    file: Cow::Borrowed(file),
+                    line,
+                    column: None,
+                });
+            result = write!(
+                f,
+                "\n{index:>2}: {}",
+                theme
+                    .span
+                    .style(format_args!("{}::{}", metadata.target(), metadata.name())),
+            )
+            .and_then(|()| match fields {
+                "" => Ok(()),
+                fields => write!(f, " with {}", theme.fields.style(fields)),
+            })
+            .and_then(|()| write!(f, "\n    at {}", config.bundle(location.as_ref())));
+            index += 1;
+            result.is_ok()
+        });
+        result
     }
 }
 
-impl Display for Bundle<'_, Option<&std::backtrace::Backtrace>> {
+impl Display for Bundle<'_, &[Frame<'_>]> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let backtrace = self.data.as_ref().map(ToString::to_string);
-        let backtrace: Backtrace = if let Some(ref backtrace) = backtrace {
-            BacktraceParser::new(backtrace).collect::<Vec<_>>().into()
-        } else {
-            Backtrace::default()
-        };
+        let frames: Vec<_> = self.data.iter().map(Frame::borrowed).collect();
+        let backtrace: Backtrace = frames.into();
         Display::fmt(&self.config.bundle(&backtrace), f)
     }
 }
+
+impl Display for Bundle<'_, Option<&[Frame<'_>]>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.config.bundle(self.data.unwrap_or_default()), f)
+    }
+}
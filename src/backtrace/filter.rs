@@ -4,6 +4,8 @@ use crate::consts::SYM_PREFIX_INIT;
 use crate::consts::SYM_PREFIX_INTERNAL;
 use crate::consts::SYM_PREFIX_PANIC;
 use crate::location::Location;
+use crate::Config;
+use crate::Verbosity;
 
 use super::Frame;
 
@@ -11,7 +13,7 @@ use super::Frame;
 pub type FrameFilter = dyn Fn(&mut Vec<Frame>) + Send + Sync + 'static;
 
 impl Frame<'_> {
-    pub(super) fn is_dependency_code(&self) -> bool {
+    pub(crate) fn is_dependency_code(&self) -> bool {
         // Inspect name.
         let Some(name) = self.name.as_deref() else {
             return true;
@@ -27,7 +29,7 @@ impl Frame<'_> {
             {
                 return true;
             }
-        };
+        }
 
         false
     }
@@ -71,7 +73,20 @@ impl Frame<'_> {
         let Some(name) = self.name() else {
             return false;
         };
-        SYM_PREFIX_INTERNAL.iter().copied().any(prefixes(name))
+        if SYM_PREFIX_INTERNAL.iter().copied().any(prefixes(name)) {
+            return true;
+        }
+
+        // Qualified paths such as `<Type as Trait>::method`, where either type
+        // or trait is internal
+        let Some(qualified) = name.strip_prefix('<') else {
+            return false;
+        };
+        let (ty, tr) = qualified.split_once(" as ").unwrap_or((qualified, ""));
+        SYM_PREFIX_INTERNAL
+            .iter()
+            .copied()
+            .any(|prefix| ty.starts_with(prefix) || tr.starts_with(prefix))
     }
 
     pub(crate) fn default_filters() -> Vec<Box<FrameFilter>> {
@@ -79,6 +94,33 @@ impl Frame<'_> {
     }
 }
 
+impl Config {
+    /// Applies registered filters, unless [`Verbosity::Full`] is selected
+    pub(crate) fn apply_filters(&self, frames: &mut Vec<Frame>) {
+        if self.selected_verbosity() == Verbosity::Full {
+            return;
+        }
+        for filter in &self.filters {
+            filter(frames);
+            frames.sort_by_key(Frame::index);
+        }
+    }
+
+    /// Applies filters to copy of `frames`, recording which of them are hidden
+    #[cfg(feature = "json")]
+    pub(crate) fn hidden_frames(&self, frames: &[Frame]) -> Vec<bool> {
+        let mut visible = frames.to_vec();
+        self.apply_filters(&mut visible);
+
+        // NOTE: filters keep remaining frames in original order
+        let mut visible = visible.iter().peekable();
+        frames
+            .iter()
+            .map(|frame| visible.next_if(|kept| *kept == frame).is_none())
+            .collect()
+    }
+}
+
 fn runtime(frames: &mut Vec<Frame>) {
     let top = frames
         .iter()
@@ -102,3 +144,32 @@ fn internal(frames: &mut Vec<Frame>) {
 fn prefixes(string: &str) -> impl Fn(&str) -> bool + '_ {
     |prefix| string.starts_with(prefix)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParsedBacktrace;
+
+    #[test]
+    fn internal_machinery() {
+        let text = "\
+   0: <core::result::Result<T,E> as terminator::context::Context<T,E>>::context
+   1: <terminator::error::Terminator as core::convert::From<E>>::from
+   2: <T as core::convert::Into<U>>::into
+   3: terminator::error::Terminator::new
+   4: <app::Config as core::str::FromStr>::from_str
+   5: app::main
+";
+        let mut frames = ParsedBacktrace::parse(text).unwrap().into_frames();
+        internal(&mut frames);
+        let names: Vec<_> = frames.iter().filter_map(Frame::name).collect();
+        assert_eq!(
+            names,
+            [
+                "<T as core::convert::Into<U>>::into",
+                "<app::Config as core::str::FromStr>::from_str",
+                "app::main",
+            ]
+        );
+    }
+}
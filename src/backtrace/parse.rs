@@ -0,0 +1,516 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+
+use crate::consts::UNKNOWN;
+use crate::location::Location;
+
+use super::Frame;
+
+/// Backtrace parsed from its textual representation
+///
+/// Understands output of [`std::backtrace::Backtrace`] and of the default panic
+/// hook, in both short and full (`RUST_BACKTRACE=full`) style. Text surrounding
+/// the backtrace, such as the rest of a log file, is ignored.
+///
+/// ```
+/// let text = "\
+/// stack backtrace:
+///    0: app::main
+///              at ./src/main.rs:4:5
+/// note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.";
+///
+/// let backtrace = terminator::ParsedBacktrace::parse(text).unwrap();
+/// assert_eq!(backtrace.frames()[0].name(), Some("app::main"));
+/// assert!(backtrace.is_short());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedBacktrace<'a> {
+    frames: Vec<Frame<'a>>,
+    short: bool,
+    diagnostics: Vec<ParseDiagnostic<'a>>,
+}
+
+impl<'a> ParsedBacktrace<'a> {
+    /// Parses backtrace from text
+    ///
+    /// Lines inside the backtrace which could not be understood are skipped and
+    /// reported in [`ParsedBacktrace::diagnostics`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text contains no frames. The error describes
+    /// whether the backtrace was disabled or unsupported, if the text says so.
+    pub fn parse(text: &'a str) -> std::result::Result<Self, BacktraceError> {
+        let mut parsed = Self {
+            frames: Vec::new(),
+            short: false,
+            diagnostics: Vec::new(),
+        };
+        let mut error = BacktraceError::Empty;
+
+        for (number, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            let mut diagnose = |reason| {
+                parsed.diagnostics.push(ParseDiagnostic {
+                    line: number + 1,
+                    text: raw,
+                    reason,
+                });
+            };
+
+            if line.is_empty() || line == "stack backtrace:" {
+                continue;
+            }
+            if line == "disabled backtrace" || line.starts_with("note: run with `RUST_BACKTRACE=1`")
+            {
+                error = BacktraceError::Disabled;
+                continue;
+            }
+            if line == "unsupported backtrace" {
+                error = BacktraceError::Unsupported;
+                continue;
+            }
+            if line.starts_with("note: Some details are omitted") {
+                parsed.short = true;
+                continue;
+            }
+            if let Some(frame) = frame(line) {
+                parsed.frames.push(frame);
+                continue;
+            }
+
+            // NOTE: anything before the first frame is not a part of backtrace
+            let Some(last) = parsed.frames.last_mut() else {
+                continue;
+            };
+
+            if let Some(location) = line.strip_prefix("at ") {
+                if last.location.is_some() {
+                    diagnose("location does not belong to any symbol");
+                } else if let Some(location) = self::location(location) {
+                    last.location = Some(location);
+                } else {
+                    diagnose("malformed location");
+                }
+            } else if raw.starts_with("      ") && symbol(line) {
+                // NOTE: symbols inlined into the same frame follow without index,
+                // innermost first
+                last.inlined = true;
+                let symbol = Frame {
+                    index: last.index,
+                    name: (line != UNKNOWN).then_some(Cow::Borrowed(line)),
+                    location: None,
+                    address: last.address,
+                    inlined: false,
+                };
+                parsed.frames.push(symbol);
+            } else {
+                diagnose("unrecognized line");
+            }
+        }
+
+        if parsed.frames.is_empty() {
+            Err(error)
+        } else {
+            Ok(parsed)
+        }
+    }
+
+    /// Parsed frames
+    #[must_use]
+    #[inline]
+    pub fn frames(&self) -> &[Frame<'a>] {
+        &self.frames
+    }
+
+    /// Consumes parsed backtrace, returning its frames
+    #[must_use]
+    #[inline]
+    pub fn into_frames(self) -> Vec<Frame<'a>> {
+        self.frames
+    }
+
+    /// Whether the backtrace notes that some details were omitted
+    #[must_use]
+    #[inline]
+    pub fn is_short(&self) -> bool {
+        self.short
+    }
+
+    /// Lines inside the backtrace which could not be parsed
+    #[must_use]
+    #[inline]
+    pub fn diagnostics(&self) -> &[ParseDiagnostic<'a>] {
+        &self.diagnostics
+    }
+}
+
+/// Parses frame line such as `  3: name` or `  3:     0x1f2e3d - name`
+pub(crate) fn frame(line: &str) -> Option<Frame<'_>> {
+    let (index, rest) = line.split_once(": ")?;
+    let index = index.parse().ok()?;
+    let rest = rest.trim_start();
+    let (address, name) = match rest.split_once(" - ") {
+        Some((address, name)) if address.starts_with("0x") => {
+            let address = usize::from_str_radix(&address[2..], 16).ok()?;
+            (Some(address), name)
+        }
+        _ => (None, rest),
+    };
+    Some(Frame {
+        index,
+        name: (name != UNKNOWN).then_some(Cow::Borrowed(name)),
+        location: None,
+        address,
+        inlined: false,
+    })
+}
+
+/// Checks whether `line` looks like name of symbol, which contains whitespace
+/// only within brackets, such as in `<T as core::any::Any>::type_id`
+fn symbol(line: &str) -> bool {
+    let mut depth = 0_usize;
+    for char in line.chars() {
+        match char {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            char if char.is_whitespace() && depth == 0 => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Parses location such as `src/main.rs:4:5`, where column is optional
+pub(crate) fn location(text: &str) -> Option<Location<'_>> {
+    let (rest, last) = text.rsplit_once(':')?;
+    let last = last.parse().ok()?;
+    let (file, line, column) = match rest.rsplit_once(':') {
+        Some((file, line)) => match line.parse() {
+            Ok(line) => (file, line, Some(last)),
+            Err(_) => (rest, last, None),
+        },
+        None => (rest, last, None),
+    };
+    Some(Location {
+        file: Cow::Borrowed(file),
+        line,
+        column,
+    })
+}
+
+/// Line of backtrace which could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDiagnostic<'a> {
+    line: usize,
+    text: &'a str,
+    reason: &'static str,
+}
+
+impl ParseDiagnostic<'_> {
+    /// Line number, starting from 1
+    #[must_use]
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Text of the line
+    #[must_use]
+    #[inline]
+    pub fn text(&self) -> &str {
+        self.text
+    }
+
+    /// Why the line could not be parsed
+    #[must_use]
+    #[inline]
+    pub fn reason(&self) -> &str {
+        self.reason
+    }
+}
+
+impl Display for ParseDiagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "line {}: {}: `{}`",
+            self.line,
+            self.reason,
+            self.text.trim()
+        )
+    }
+}
+
+/// Error for when text contains no backtrace frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BacktraceError {
+    /// Backtrace capture was disabled by the environment
+    Disabled,
+    /// Backtraces are not supported on the platform
+    Unsupported,
+    /// No frames were found in the text
+    Empty,
+}
+
+impl Display for BacktraceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            Self::Disabled => "backtrace was disabled",
+            Self::Unsupported => "backtrace is not supported",
+            Self::Empty => "text contains no backtrace frames",
+        })
+    }
+}
+
+impl Error for BacktraceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vec<Frame<'_>> {
+        ParsedBacktrace::parse(text).unwrap().into_frames()
+    }
+
+    #[test]
+    fn full_backtrace() {
+        let backtrace = "\
+   0: anyhow::error::<impl anyhow::Error>::msg
+             at /home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/anyhow-1.0.81/src/error.rs:83:36
+   1: anyhow::__private::format_err
+             at /home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/anyhow-1.0.81/src/lib.rs:688:13
+   2: aoc2023::day1::exec
+             at ./aoc2023/src/day1.rs:52:17
+   3: aoc2023::day1::first
+             at ./aoc2023/src/day1.rs:22:5
+   4: core::ops::function::FnOnce::call_once
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/core/src/ops/function.rs:250:5
+   5: <F as aoc::Eval<A>>::eval
+             at ./src/lib.rs:17:9
+   6: aoc::main
+             at ./src/main.rs:46:18
+   7: core::ops::function::FnOnce::call_once
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/core/src/ops/function.rs:250:5
+   8: std::sys_common::backtrace::__rust_begin_short_backtrace
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/sys_common/backtrace.rs:155:18
+   9: std::rt::lang_start::{{closure}}
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/rt.rs:166:18
+  10: core::ops::function::impls::<impl core::ops::function::FnOnce<A> for &F>::call_once
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/core/src/ops/function.rs:284:13
+  11: std::panicking::try::do_call
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/panicking.rs:554:40
+  12: std::panicking::try
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/panicking.rs:518:19
+  13: std::panic::catch_unwind
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/panic.rs:142:14
+  14: std::rt::lang_start_internal::{{closure}}
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/rt.rs:148:48
+  15: std::panicking::try::do_call
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/panicking.rs:554:40
+  16: std::panicking::try
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/panicking.rs:518:19
+  17: std::panic::catch_unwind
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/panic.rs:142:14
+  18: std::rt::lang_start_internal
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/rt.rs:148:20
+  19: std::rt::lang_start
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/rt.rs:165:17
+  20: main
+  21: <unknown>
+  22: __libc_start_main
+  23: _start
+";
+        let backtrace = ParsedBacktrace::parse(backtrace).unwrap();
+        assert_eq!(
+            backtrace.frames().len(),
+            24,
+            "Backtrace had wrong length {backtrace:#?}"
+        );
+        assert!(backtrace.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn plain_frame() {
+        assert_eq!(
+            parse("  20: main"),
+            [Frame {
+                index: 20,
+                name: Some(Cow::Borrowed("main")),
+                location: None,
+                address: None,
+                inlined: false,
+            }]
+        );
+        assert_eq!(
+            parse("  21: <unknown>"),
+            [Frame {
+                index: 21,
+                name: None,
+                location: None,
+                address: None,
+                inlined: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn located_frame() {
+        assert_eq!(
+            parse("   6: aoc::main\n             at ./src/main.rs:46:18"),
+            [Frame {
+                index: 6,
+                name: Some(Cow::Borrowed("aoc::main")),
+                location: Some(Location {
+                    file: Cow::Borrowed("./src/main.rs"),
+                    line: 46,
+                    column: Some(18),
+                }),
+                address: None,
+                inlined: false,
+            }]
+        );
+        let frame = "   6: aoc::main\n             at ./src/main.rs:46";
+        assert_eq!(
+            parse(frame)[0].location,
+            Some(Location {
+                file: Cow::Borrowed("./src/main.rs"),
+                line: 46,
+                column: None,
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_frame() {
+        let text = "   x: broken\n   7: aoc::main\n             at ./src/main.rs:line:col";
+        let backtrace = ParsedBacktrace::parse(text).unwrap();
+        assert_eq!(
+            backtrace.frames(),
+            [Frame {
+                index: 7,
+                name: Some(Cow::Borrowed("aoc::main")),
+                location: None,
+                address: None,
+                inlined: false,
+            }]
+        );
+        let diagnostic = &backtrace.diagnostics()[0];
+        assert_eq!(diagnostic.line(), 3);
+        assert_eq!(diagnostic.reason(), "malformed location");
+    }
+
+    #[test]
+    fn full_style() {
+        let text = "\
+stack backtrace:
+   0:     0x55d0c1a3b2c4 - std::backtrace_rs::backtrace::libunwind::trace::h5a5b8284f2d0c266
+                               at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/../../backtrace/src/backtrace/libunwind.rs:104:5
+   1:     0x55d0c1a3b2c4 - <unknown>
+";
+        let frames = parse(text);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].address, Some(0x55d0_c1a3_b2c4));
+        assert_eq!(
+            frames[0].name(),
+            Some("std::backtrace_rs::backtrace::libunwind::trace::h5a5b8284f2d0c266")
+        );
+        assert_eq!(frames[0].location.as_ref().map(Location::line), Some(104));
+        assert_eq!(frames[1].name(), None);
+    }
+
+    #[test]
+    fn inlined_frames() {
+        let text = "\
+   4: core::ops::function::FnOnce::call_once
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/core/src/ops/function.rs:250:5
+      std::rt::lang_start::{{closure}}
+             at /rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/std/src/rt.rs:166:18
+   5: main
+";
+        let frames = parse(text);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].index, 4);
+        assert!(frames[0].inlined && !frames[1].inlined);
+        assert_eq!(frames[1].name(), Some("std::rt::lang_start::{{closure}}"));
+        assert_eq!(frames[1].location.as_ref().map(Location::line), Some(166));
+    }
+
+    #[test]
+    fn indented_text() {
+        let text = "\
+   4: app::run
+      called from the scheduler
+      <app::Task as core::future::Future>::poll
+   5: main
+";
+        let backtrace = ParsedBacktrace::parse(text).unwrap();
+        let names: Vec<_> = backtrace.frames().iter().filter_map(Frame::name).collect();
+        assert_eq!(
+            names,
+            [
+                "app::run",
+                "<app::Task as core::future::Future>::poll",
+                "main"
+            ]
+        );
+        assert_eq!(backtrace.diagnostics().len(), 1);
+        assert_eq!(backtrace.diagnostics()[0].line(), 2);
+    }
+
+    #[test]
+    fn short_style() {
+        let text = "\
+thread 'main' panicked at src/main.rs:2:5:
+boom
+stack backtrace:
+   0: rust_begin_unwind
+   1: app::main
+             at ./src/main.rs:2:5
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.
+";
+        let backtrace = ParsedBacktrace::parse(text).unwrap();
+        assert!(backtrace.is_short());
+        assert_eq!(backtrace.frames().len(), 2);
+        assert!(backtrace.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn missing_backtrace() {
+        assert_eq!(
+            ParsedBacktrace::parse("disabled backtrace"),
+            Err(BacktraceError::Disabled)
+        );
+        let text = "thread 'main' panicked at src/main.rs:2:5:\nboom\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace";
+        assert_eq!(ParsedBacktrace::parse(text), Err(BacktraceError::Disabled));
+        assert_eq!(
+            ParsedBacktrace::parse("unsupported backtrace"),
+            Err(BacktraceError::Unsupported)
+        );
+        assert_eq!(
+            ParsedBacktrace::parse("just some text"),
+            Err(BacktraceError::Empty)
+        );
+    }
+
+    #[test]
+    fn log_file() {
+        let text = "\
+[2024-05-01T10:00:00Z INFO  app] starting
+[2024-05-01T10:00:01Z ERROR app] request failed
+   0: app::handler
+             at ./src/handler.rs:10:9
+   1: app::main
+             at ./src/main.rs:20:5
+[2024-05-01T10:00:02Z INFO  app] shutting down
+";
+        let backtrace = ParsedBacktrace::parse(text).unwrap();
+        assert_eq!(backtrace.frames().len(), 2);
+        assert_eq!(backtrace.diagnostics().len(), 1);
+        assert_eq!(backtrace.diagnostics()[0].line(), 7);
+    }
+}
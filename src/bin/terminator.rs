@@ -0,0 +1,291 @@
+//! Prettifies panics and backtraces in output of Rust programs
+
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::stderr;
+use std::io::stdin;
+use std::io::stdout;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::process;
+use std::process::Command;
+use std::process::ExitCode;
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+
+use terminator::Config;
+use terminator::Prettifier;
+use terminator::Theme;
+use terminator::Verbosity;
+
+const USAGE: &str = "\
+Usage: terminator [OPTIONS] [FILE]...
+       terminator [OPTIONS] run [--] <COMMAND> [ARGS]...
+
+Prettifies panics and backtraces in FILEs (or standard input, also when FILE is -).
+With `run`, COMMAND is spawned with `RUST_BACKTRACE` set and its output is
+prettified instead, while exiting with the same status as COMMAND.
+Text which is not part of a panic or backtrace is passed through unchanged.
+
+Options:
+  -t, --theme <THEME>          dark, light or none [default: dark on terminal, none otherwise]
+  -v, --verbosity <VERBOSITY>  minimal, medium or full [default: medium, or `RUST_BACKTRACE` with `run`]
+  -p, --full-paths             Print paths unaltered instead of shortening them
+  -h, --help                   Print help
+";
+
+/// Time without new input, after which report being read is rendered
+const IDLE: Duration = Duration::from_millis(100);
+
+struct Options {
+    theme: Option<Theme>,
+    verbosity: Option<Verbosity>,
+    full_paths: bool,
+    files: Vec<String>,
+    command: Option<Vec<String>>,
+}
+
+fn options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        theme: None,
+        verbosity: None,
+        full_paths: false,
+        files: Vec::new(),
+        command: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(String::new()),
+            "-t" | "--theme" => {
+                options.theme = Some(match args.next().as_deref() {
+                    Some("dark") => Theme::dark(),
+                    Some("light") => Theme::light(),
+                    Some("none") => Theme::blank(),
+                    _ => return Err(format!("invalid value for `{arg}`")),
+                });
+            }
+            "-v" | "--verbosity" => {
+                options.verbosity = Some(match args.next().as_deref() {
+                    Some("minimal") => Verbosity::Minimal,
+                    Some("medium") => Verbosity::Medium,
+                    Some("full") => Verbosity::Full,
+                    _ => return Err(format!("invalid value for `{arg}`")),
+                });
+            }
+            "-p" | "--full-paths" => options.full_paths = true,
+            "run" if options.files.is_empty() => {
+                let mut command: Vec<_> = args.by_ref().collect();
+                if command.first().is_some_and(|arg| arg == "--") {
+                    command.remove(0);
+                }
+                if command.is_empty() {
+                    return Err("missing command to run".to_owned());
+                }
+                options.command = Some(command);
+            }
+            "--" => options.files.extend(args.by_ref()),
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(format!("unknown option `{flag}`"));
+            }
+            _ => options.files.push(arg),
+        }
+    }
+    if options.files.is_empty() && options.command.is_none() {
+        options.files.push("-".to_owned());
+    }
+    Ok(options)
+}
+
+fn prettify(
+    prettifier: &mut Prettifier,
+    input: impl BufRead + Send,
+    output: &mut dyn Write,
+) -> io::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for line in input.split(b'\n') {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        loop {
+            // NOTE: end of backtrace without note is known only from the next line,
+            // so it's rendered once input pauses instead of waiting for it
+            let line = match receiver.recv_timeout(IDLE) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    prettifier.finish(output)?;
+                    output.flush()?;
+                    match receiver.recv() {
+                        Ok(line) => line,
+                        Err(_) => break,
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }?;
+            let line = String::from_utf8_lossy(&line);
+            prettifier.line(line.strip_suffix('\r').unwrap_or(&line), output)?;
+        }
+        prettifier.finish(output)
+    })
+}
+
+/// Creates [`Config`] for reports written to output, which is a `terminal`
+fn config(options: &Options, verbosity: Verbosity, terminal: bool) -> Config {
+    let theme = options.theme.clone().unwrap_or_else(|| {
+        if terminal {
+            Theme::dark()
+        } else {
+            Theme::blank()
+        }
+    });
+    Config::new()
+        .theme(theme)
+        .verbosity(verbosity)
+        .shorten_paths(!options.full_paths)
+}
+
+fn files(options: &Options) -> ExitCode {
+    let verbosity = options.verbosity.unwrap_or(Verbosity::Medium);
+    let terminal = stdout().is_terminal();
+    let config = config(options, verbosity, terminal);
+    let mut prettifier = Prettifier::new(&config).terminal(terminal);
+    let mut output = stdout().lock();
+
+    let mut code = ExitCode::SUCCESS;
+    for file in &options.files {
+        let result = if file == "-" {
+            prettify(&mut prettifier, BufReader::new(stdin()), &mut output)
+        } else {
+            File::open(file)
+                .and_then(|input| prettify(&mut prettifier, BufReader::new(input), &mut output))
+        };
+        if let Err(error) = result {
+            eprintln!("error: {file}: {error}");
+            code = ExitCode::FAILURE;
+        }
+    }
+    code
+}
+
+fn run(options: &Options, command: &[String]) -> io::Result<i32> {
+    // NOTE: `RUST_BACKTRACE` set by user is respected, unless overridden by option
+    let verbosity = options
+        .verbosity
+        .or_else(Verbosity::panic)
+        .unwrap_or(Verbosity::Medium);
+    let mut child = Command::new(&command[0])
+        .args(&command[1..])
+        .env("RUST_BACKTRACE", verbosity.env())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // NOTE: panics of tests are printed to stdout by the test harness
+    let (stdout_terminal, stderr_terminal) = (stdout().is_terminal(), stderr().is_terminal());
+    let stdout_config = config(options, verbosity, stdout_terminal);
+    let stderr_config = config(options, verbosity, stderr_terminal);
+    let child_stdout = child.stdout.take().expect("stdout was piped");
+    let child_stderr = child.stderr.take().expect("stderr was piped");
+    thread::scope(|scope| {
+        let forward = scope.spawn(|| {
+            let mut prettifier = Prettifier::new(&stdout_config).terminal(stdout_terminal);
+            prettify(&mut prettifier, BufReader::new(child_stdout), &mut stdout())
+        });
+        let mut prettifier = Prettifier::new(&stderr_config).terminal(stderr_terminal);
+        let result = prettify(&mut prettifier, BufReader::new(child_stderr), &mut stderr());
+        forward
+            .join()
+            .expect("forwarding thread panicked")
+            .and(result)
+    })?;
+
+    let status = child.wait()?;
+    #[cfg(unix)]
+    if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&status) {
+        return Ok(128 + signal);
+    }
+    Ok(status.code().unwrap_or(1))
+}
+
+fn main() -> ExitCode {
+    let options = match options(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) if error.is_empty() => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprint!("error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let Some(command) = &options.command else {
+        return files(&options);
+    };
+    match run(&options, command) {
+        Ok(code) => process::exit(code),
+        Err(error) => {
+            eprintln!("error: {}: {error}", command[0]);
+            ExitCode::from(127)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        options(args.iter().copied().map(String::from))
+    }
+
+    #[test]
+    fn arguments() {
+        let options = parse(&["-t", "none", "first", "--", "-v"]).unwrap();
+        assert!(options.theme.is_some());
+        assert!(!options.full_paths);
+        assert!(parse(&["--full-paths"]).unwrap().full_paths);
+        assert_eq!(options.files, ["first", "-v"]);
+        assert_eq!(parse(&[]).unwrap().files, ["-"]);
+
+        let command = |args| parse(args).unwrap().command.unwrap();
+        assert_eq!(command(&["run", "cargo", "test"]), ["cargo", "test"]);
+        assert_eq!(
+            command(&["-v", "full", "run", "--", "ls", "-l"]),
+            ["ls", "-l"]
+        );
+        assert_eq!(command(&["run", "--", "--", "foo"]), ["--", "foo"]);
+
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "--"]).is_err());
+        assert!(parse(&["--theme", "blue"]).is_err());
+        assert!(parse(&["--unknown"]).is_err());
+        assert_eq!(parse(&["--help"]).err().as_deref(), Some(""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exit_status() {
+        let options = parse(&["-t", "none", "run", "true"]).unwrap();
+        let run = |args: &[&str]| {
+            run(
+                &options,
+                &args.iter().map(|arg| (*arg).to_owned()).collect::<Vec<_>>(),
+            )
+        };
+        assert_eq!(run(&["sh", "-c", "exit 3"]).unwrap(), 3);
+        assert_eq!(run(&["true"]).unwrap(), 0);
+        assert_eq!(run(&["sh", "-c", "kill -9 $$"]).unwrap(), 128 + 9);
+        assert!(run(&["/nonexistent/command"]).is_err());
+    }
+}
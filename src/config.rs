@@ -1,9 +1,17 @@
 use std::env;
 use std::panic;
+use std::path::PathBuf;
+use std::sync::OnceLock;
 use std::thread;
 
 use crate::consts::BACKTRACE;
 use crate::consts::LIB_BACKTRACE;
+use crate::exit::ExitCodeMapper;
+use crate::friendly::Metadata;
+use crate::link;
+use crate::link::Links;
+use crate::location::Roots;
+use crate::Format;
 use crate::Frame;
 use crate::FrameFilter;
 use crate::InstallError;
@@ -18,11 +26,23 @@ pub(crate) struct Bundle<'a, T> {
 
 /// Configuration influencing appearance of displayed messages
 #[must_use = "`Config` is useless unless used in panic hook or installed"]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     pub(crate) filters: Vec<Box<FrameFilter>>,
     pub(crate) theme: Theme,
     pub(crate) error: Verbosity,
     pub(crate) panic: Verbosity,
+    pub(crate) format: Format,
+    pub(crate) dedup: bool,
+    pub(crate) shorten: Option<Roots>,
+    pub(crate) links: Option<Links>,
+    pub(crate) hyperlinks: OnceLock<bool>,
+    pub(crate) web_links: bool,
+    pub(crate) snippets: bool,
+    pub(crate) crash_dir: Option<PathBuf>,
+    pub(crate) metadata: Option<Metadata>,
+    pub(crate) friendly: bool,
+    pub(crate) codes: Vec<Box<ExitCodeMapper>>,
 }
 
 impl Config {
@@ -46,6 +66,17 @@ impl Config {
             theme: Theme::new(),
             error: Verbosity::Minimal,
             panic: Verbosity::Minimal,
+            format: Format::Pretty,
+            dedup: false,
+            shorten: None,
+            links: None,
+            hyperlinks: OnceLock::new(),
+            web_links: false,
+            snippets: false,
+            crash_dir: None,
+            metadata: None,
+            friendly: false,
+            codes: Vec::new(),
         }
     }
 
@@ -59,7 +90,18 @@ impl Config {
             filters: Frame::default_filters(),
             error: Verbosity::error().unwrap_or_default(),
             panic: Verbosity::panic().unwrap_or_default(),
+            format: Format::env().unwrap_or_default(),
+            dedup: false,
+            shorten: None,
+            links: link::env(),
+            hyperlinks: OnceLock::new(),
+            web_links: false,
+            snippets: false,
+            crash_dir: None,
+            metadata: None,
+            friendly: false,
             theme: Theme::new(),
+            codes: Vec::new(),
         }
     }
 
@@ -112,6 +154,41 @@ impl Config {
         self
     }
 
+    /// Set [`Format`] of printed errors
+    #[inline]
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Remove text repeated from sources in error chain
+    ///
+    /// When error message ends with a separator such as `": "` followed by
+    /// message of its source, the separator and repeated part are trimmed.
+    /// Source enclosed in parentheses or square brackets anywhere in message,
+    /// such as `"failed (No such file) at startup"`, is removed. Message equal
+    /// to its source is dropped. Source merely contained in message as plain
+    /// text is kept, as it may be just a common word. Errors are always shown
+    /// unaltered with [`Verbosity::Full`].
+    #[inline]
+    pub fn dedup(mut self, enabled: bool) -> Self {
+        self.dedup = enabled;
+        self
+    }
+
+    /// Shorten paths of printed locations
+    ///
+    /// Current working directory (at the time of this call) is stripped, home
+    /// directory is replaced with `~`, registry paths are collapsed into
+    /// `crate-version/...` and paths of standard library into `<rust>/...`.
+    /// Paths are always shown unaltered with [`Verbosity::Full`] and in
+    /// [`Format::Json`].
+    #[inline]
+    pub fn shorten_paths(mut self, enabled: bool) -> Self {
+        self.shorten = enabled.then(Roots::new);
+        self
+    }
+
     /// Add filter for backtrace filtering
     #[inline]
     pub fn filter(mut self, filter: Box<FrameFilter>) -> Self {
@@ -25,6 +25,10 @@ constified!(
     //UNKNOWN_LINE => unknown!("line"),
     BACKTRACE => "RUST_BACKTRACE",
     LIB_BACKTRACE => "RUST_LIB_BACKTRACE",
+    FORMAT => "TERMINATOR_FORMAT",
+    LINKS => "TERMINATOR_LINKS",
+    HOME => "HOME",
+    RUST_PREFIX => "<rust>/",
 );
 
 constified!(
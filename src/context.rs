@@ -0,0 +1,146 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::error::Message;
+use crate::Terminator;
+
+mod sealed {
+    use std::error::Error;
+
+    use crate::Terminator;
+
+    pub trait Sealed {}
+
+    impl<T, E> Sealed for Result<T, E> where E: Error + Send + Sync + 'static {}
+    impl<T> Sealed for Result<T, Terminator> {}
+    impl<T> Sealed for Option<T> {}
+}
+
+/// Extension trait for attaching context to errors
+///
+/// Context is added as new outermost error in the chain, with the original error
+/// as its source. This behaves the same regardless of selected backend.
+pub trait Context<T, E>: sealed::Sealed {
+    /// Wrap the error value with additional context
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Terminator`] wrapped in context if `self` holds an error (or `None`).
+    fn context<C>(self, context: C) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static;
+
+    /// Wrap the error value with additional context that is evaluated lazily
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Terminator`] wrapped in context if `self` holds an error (or `None`).
+    fn with_context<C, F>(self, context: F) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> Context<T, E> for Result<T, E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn context<C>(self, context: C) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(Terminator::from(error).wrap(context)),
+        }
+    }
+
+    #[track_caller]
+    fn with_context<C, F>(self, context: F) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(Terminator::from(error).wrap(context())),
+        }
+    }
+}
+
+impl<T> Context<T, Terminator> for Result<T, Terminator> {
+    fn context<C>(self, context: C) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        self.map_err(|error| error.wrap(context))
+    }
+
+    fn with_context<C, F>(self, context: F) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|error| error.wrap(context()))
+    }
+}
+
+impl<T> Context<T, Infallible> for Option<T> {
+    #[track_caller]
+    fn context<C>(self, context: C) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        match self {
+            Some(value) => Ok(value),
+            None => Err(Terminator::from(Message(context))),
+        }
+    }
+
+    #[track_caller]
+    fn with_context<C, F>(self, context: F) -> Result<T, Terminator>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        match self {
+            Some(value) => Ok(value),
+            None => Err(Terminator::from(Message(context()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn messages(error: &Terminator) -> Vec<String> {
+        error.chain().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn result() {
+        let error = Err::<(), _>(io::Error::other("root cause"))
+            .context("middle")
+            .with_context(|| "outer")
+            .unwrap_err();
+        assert_eq!(messages(&error), ["outer", "middle", "root cause"]);
+
+        // Context of a report wraps its chain and keeps the original location
+        let report = crate::error::failure();
+        let location = report.location();
+        let error = Err::<(), _>(report).with_context(|| "top").unwrap_err();
+        assert_eq!(messages(&error), ["top", "outer", "root cause"]);
+        assert_eq!(error.location(), location);
+    }
+
+    #[test]
+    fn option() {
+        let error = None::<()>.context("missing").context("outer").unwrap_err();
+        assert_eq!(messages(&error), ["outer", "missing"]);
+    }
+}
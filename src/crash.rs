@@ -0,0 +1,209 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::Config;
+
+impl Config {
+    /// Save uncolored copy of every panic and fatal error report into `dir`
+    ///
+    /// Fatal errors are those reported by [`crate::Outcome`]; errors returned
+    /// from `main` directly are only printed. Reports are named after their kind, time of creation and process
+    /// id. The directory is created when the first report is written. With JSON
+    /// format, path of the report is added as `report_file` field of the printed
+    /// object instead of a notice on separate line.
+    #[inline]
+    pub fn crash_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.crash_dir = Some(dir.into());
+        self
+    }
+
+    /// Writes `report` and saves it into crash report file, if enabled
+    pub(crate) fn emit(
+        &self,
+        f: &mut dyn fmt::Write,
+        kind: &str,
+        report: impl Display,
+    ) -> fmt::Result {
+        let Some(dir) = self.crash_dir.as_deref() else {
+            return write!(f, "{report}");
+        };
+
+        let report = report.to_string();
+        let saved = save(dir, kind, &report);
+        let newline = report.ends_with('\n');
+
+        #[cfg(feature = "json")]
+        if self.format == crate::Format::Json {
+            let mut value: serde_json::Value =
+                serde_json::from_str(&report).map_err(|_| fmt::Error)?;
+            match saved {
+                Ok(path) => value["report_file"] = path.display().to_string().into(),
+                Err(error) => value["report_error"] = error.to_string().into(),
+            }
+            write!(f, "{value}")?;
+            if newline {
+                f.write_char('\n')?;
+            }
+            return Ok(());
+        }
+
+        f.write_str(&report)?;
+        // NOTE: notice is put on separate line, keeping trailing newline as is
+        if !newline {
+            f.write_char('\n')?;
+        }
+
+        let theme = &self.theme;
+        match saved {
+            Ok(path) => write!(
+                f,
+                "{} {}",
+                theme.header.style("The report was saved to:"),
+                theme.file.style(path.display()),
+            )?,
+            Err(error) => write!(
+                f,
+                "{} {error}",
+                theme.header.style("The report could not be saved:"),
+            )?,
+        }
+        if newline {
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn save(dir: &Path, kind: &str, report: &str) -> io::Result<PathBuf> {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let name = format!("{kind}-{time}-{}", process::id());
+    fs::create_dir_all(dir)?;
+
+    // NOTE: reports created in the same millisecond get numbered suffix
+    for attempt in 0.. {
+        let path = match attempt {
+            0 => dir.join(format!("{name}.log")),
+            _ => dir.join(format!("{name}-{attempt}.log")),
+        };
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(strip(report).as_bytes())?;
+                return Ok(path);
+            }
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(error) => return Err(error),
+        }
+    }
+    unreachable!("unbounded range of attempts")
+}
+
+/// Removes ANSI escape sequences from text
+fn strip(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(char) = chars.next() {
+        if char != '\x1b' {
+            result.push(char);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => _ = chars.by_ref().find(|char| ('\x40'..='\x7e').contains(char)),
+            // NOTE: operating system commands (such as hyperlinks) end with BEL or ESC \
+            Some(']') => {
+                while let Some(char) = chars.next() {
+                    if char == '\x07' {
+                        break;
+                    }
+                    if char == '\x1b' {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+    use crate::Style;
+
+    #[test]
+    fn stripping() {
+        let styled = Style::new().fg(Color::Red).bg(Color::Blue).style("text");
+        assert_eq!(
+            strip(&format!("before {styled} after")),
+            "before text after"
+        );
+        assert_eq!(
+            strip(
+                "see \x1b]8;;file:///src/main.rs\x1b\\main\x1b]8;;\x1b\\ and \x1b]0;title\x07done"
+            ),
+            "see main and done"
+        );
+    }
+
+    #[test]
+    fn saving() {
+        let dir = std::env::temp_dir().join(format!("terminator-{}", process::id()));
+        let config = Config::blank().crash_dir(&dir);
+        let mut output = String::new();
+        config
+            .emit(&mut output, "error", "\x1b[31mreport\x1b[0m\n")
+            .unwrap();
+
+        let path = fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "report\n");
+        assert!(output.contains(&path.display().to_string()));
+        assert_eq!(output.matches('\n').count(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        let dir = std::env::temp_dir().join(format!("terminator-json-{}", process::id()));
+        let config = Config::blank().format(crate::Format::Json).crash_dir(&dir);
+        let error = crate::error::failure();
+        let mut output = String::new();
+        config
+            .emit(&mut output, "error", config.report(&error))
+            .unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["chain"], serde_json::json!(["outer", "root cause"]));
+        let path = value["report_file"].as_str().unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(saved["chain"], value["chain"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unique() {
+        let dir = std::env::temp_dir().join(format!("terminator-unique-{}", process::id()));
+        let paths: Vec<_> = (0..3)
+            .map(|_| save(&dir, "error", "report").unwrap())
+            .collect();
+        assert_ne!(paths[0], paths[1]);
+        assert_ne!(paths[1], paths[2]);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,22 +1,41 @@
-use std::backtrace::Backtrace;
 use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result;
 use std::fmt::Write;
+use std::panic;
+#[cfg(feature = "anyhow")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "tracing")]
+use tracing_error::SpanTrace;
+#[cfg(feature = "tracing")]
+use tracing_error::SpanTraceStatus;
 
+#[cfg(feature = "anyhow")]
+use crate::backtrace::parse_std;
+#[cfg(not(feature = "anyhow"))]
+use crate::backtrace::Capture;
+use crate::config::Bundle;
 use crate::indent::Indent;
+use crate::location::Location;
+use crate::section::Help;
+use crate::trace::ReturnTrace;
 use crate::Config;
+use crate::Format;
+use crate::Frame;
+use crate::Verbosity;
 use crate::GLOBAL_SETTINGS;
 
+mod dedup;
 #[cfg(feature = "eyre")]
 mod eyreimpl;
 #[cfg(not(any(feature = "anyhow", feature = "eyre")))]
 mod stdimpl;
 
 #[cfg(not(any(feature = "anyhow", feature = "eyre")))]
-type Inner = Box<stdimpl::DynError>;
+type Inner = stdimpl::DynError;
 #[cfg(feature = "anyhow")]
 type Inner = anyhow::Error;
 #[cfg(feature = "eyre")]
@@ -24,61 +43,378 @@ type Inner = eyre::Report;
 
 /// Why not use this in main function as `Error` value? It's so pretty :)
 pub struct Terminator {
+    // NOTE: boxed together to keep `Result<T, Terminator>` small
+    pub(crate) report: Box<Report>,
+}
+
+pub(crate) struct Report {
     inner: Inner,
+    pub(crate) help: Vec<Help>,
+    pub(crate) exit: Option<u8>,
+    location: &'static panic::Location<'static>,
+    pub(crate) trace: ReturnTrace,
+    #[cfg(feature = "anyhow")]
+    frames: OnceLock<Vec<Frame<'static>>>,
+    #[cfg(all(feature = "anyhow", feature = "tracing"))]
+    spantrace: SpanTrace,
 }
 
 impl Terminator {
+    #[track_caller]
     fn new(inner: Inner) -> Self {
-        Self { inner }
+        let report = Box::new(Report {
+            inner,
+            help: Vec::new(),
+            exit: None,
+            location: panic::Location::caller(),
+            trace: ReturnTrace::default(),
+            #[cfg(feature = "anyhow")]
+            frames: OnceLock::new(),
+            #[cfg(all(feature = "anyhow", feature = "tracing"))]
+            spantrace: SpanTrace::capture(),
+        });
+        Self { report }
+    }
+
+    /// Creates [`Terminator`] from printable message
+    ///
+    /// Backtrace is captured the same way as with conversion from [`Error`].
+    #[must_use]
+    #[track_caller]
+    pub fn msg<M>(message: M) -> Self
+    where
+        M: Display + Send + Sync + 'static,
+    {
+        Self::from(Message(message))
     }
 
-    fn chain(&self) -> impl Iterator<Item = &(dyn Error + 'static)> {
-        self.inner.chain()
+    /// Creates [`Terminator`] from boxed trait object
+    ///
+    /// Backtrace is captured the same way as with conversion from [`Error`].
+    #[must_use]
+    #[track_caller]
+    pub fn from_boxed(error: Box<dyn Error + Send + Sync + 'static>) -> Self {
+        #[cfg(not(any(feature = "anyhow", feature = "eyre")))]
+        let inner = stdimpl::DynError::new(error);
+        #[cfg(feature = "anyhow")]
+        let inner = anyhow::anyhow!(error);
+        #[cfg(feature = "eyre")]
+        let inner = eyre::eyre!(error);
+        Self::new(inner)
     }
 
     #[cfg(not(any(feature = "anyhow", feature = "eyre")))]
-    fn backtrace(&self) -> Option<&Backtrace> {
-        self.inner.backtrace()
+    pub(crate) fn wrap<C>(mut self, context: C) -> Self
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        self.report.inner = self.report.inner.context(context);
+        self
     }
 
     #[cfg(feature = "anyhow")]
-    fn backtrace(&self) -> Option<&Backtrace> {
-        let backtrace = self.inner.backtrace();
-        if backtrace.status() == std::backtrace::BacktraceStatus::Captured {
-            Some(backtrace)
-        } else {
-            None
+    pub(crate) fn wrap<C>(mut self, context: C) -> Self
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        self.report.inner = self.report.inner.context(context);
+        self
+    }
+
+    #[cfg(feature = "eyre")]
+    pub(crate) fn wrap<C>(mut self, context: C) -> Self
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        self.report.inner = self.report.inner.wrap_err(context);
+        self
+    }
+
+    /// Checks whether error of type `E` is present in the chain
+    ///
+    /// This includes context attached through [`Context`](crate::Context).
+    #[must_use]
+    pub fn is<E>(&self) -> bool
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.downcast_ref::<E>().is_some()
+    }
+
+    /// Returns reference to first error of type `E` in the chain
+    ///
+    /// This includes context attached through [`Context`](crate::Context).
+    #[must_use]
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        // NOTE: `anyhow` and `eyre` hide errors created from boxed trait object
+        // behind a private wrapper, which is only accessible as the box itself
+        #[cfg(any(feature = "anyhow", feature = "eyre"))]
+        if let Some(error) = self
+            .report
+            .inner
+            .downcast_ref::<Box<dyn Error + Send + Sync>>()
+            .and_then(|boxed| boxed.downcast_ref::<E>())
+        {
+            return Some(error);
+        }
+        self.report
+            .inner
+            .downcast_ref::<E>()
+            .or_else(|| self.chain().find_map(<dyn Error>::downcast_ref::<E>))
+    }
+
+    /// Returns mutable reference to error of type `E`
+    ///
+    /// Sources can't be accessed mutably, so only context attached through
+    /// [`Context`](crate::Context) and the error it was attached to are searched.
+    #[must_use]
+    pub fn downcast_mut<E>(&mut self) -> Option<&mut E>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.report.inner.downcast_mut::<E>()
+    }
+
+    /// Attempts to take out error of type `E`
+    ///
+    /// The same errors as with [`Terminator::downcast_mut`] are considered.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` if no error of type `E` is found.
+    pub fn downcast<E>(mut self) -> std::result::Result<E, Self>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        match self.report.inner.downcast::<E>() {
+            Ok(error) => Ok(error),
+            Err(inner) => {
+                self.report.inner = inner;
+                Err(self)
+            }
+        }
+    }
+
+    /// Location where the error was created or converted into [`Terminator`]
+    ///
+    /// With `eyre` backend, location where [`eyre::Report`] was created is preferred.
+    #[must_use]
+    pub fn location(&self) -> &'static panic::Location<'static> {
+        #[cfg(feature = "eyre")]
+        if let Some(location) = self
+            .report
+            .inner
+            .handler()
+            .downcast_ref::<eyreimpl::BacktraceHandler>()
+            .and_then(eyreimpl::BacktraceHandler::location)
+        {
+            return location;
+        }
+        self.report.location
+    }
+
+    pub(crate) fn chain(&self) -> impl Iterator<Item = &(dyn Error + 'static)> {
+        self.report.inner.chain()
+    }
+
+    #[cfg(not(any(feature = "anyhow", feature = "eyre")))]
+    pub(crate) fn frames(&self) -> Option<&[Frame<'static>]> {
+        self.report.inner.backtrace().map(Capture::frames)
+    }
+
+    // NOTE: `anyhow` only exposes backtrace from standard library, so it has to be
+    // parsed. Frames are cached, so it's done only once.
+    #[cfg(feature = "anyhow")]
+    pub(crate) fn frames(&self) -> Option<&[Frame<'static>]> {
+        let backtrace = self.report.inner.backtrace();
+        if backtrace.status() != std::backtrace::BacktraceStatus::Captured {
+            return None;
         }
+        Some(self.report.frames.get_or_init(|| parse_std(backtrace)))
     }
 
     #[cfg(feature = "eyre")]
-    fn backtrace(&self) -> Option<&Backtrace> {
-        self.inner
+    pub(crate) fn frames(&self) -> Option<&[Frame<'static>]> {
+        self.report
+            .inner
             .handler()
             .downcast_ref::<eyreimpl::BacktraceHandler>()
             .and_then(eyreimpl::BacktraceHandler::backtrace)
+            .map(Capture::frames)
+    }
+
+    #[cfg(all(feature = "tracing", not(any(feature = "anyhow", feature = "eyre"))))]
+    fn spantrace(&self) -> Option<&SpanTrace> {
+        Some(self.report.inner.spantrace())
+            .filter(|spantrace| spantrace.status() == SpanTraceStatus::CAPTURED)
+    }
+
+    #[cfg(all(feature = "tracing", feature = "anyhow"))]
+    fn spantrace(&self) -> Option<&SpanTrace> {
+        Some(&self.report.spantrace)
+            .filter(|spantrace| spantrace.status() == SpanTraceStatus::CAPTURED)
+    }
+
+    #[cfg(all(feature = "tracing", feature = "eyre"))]
+    fn spantrace(&self) -> Option<&SpanTrace> {
+        self.report
+            .inner
+            .handler()
+            .downcast_ref::<eyreimpl::BacktraceHandler>()
+            .map(eyreimpl::BacktraceHandler::spantrace)
+            .filter(|spantrace| spantrace.status() == SpanTraceStatus::CAPTURED)
     }
 }
 
 impl Debug for Terminator {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         if f.alternate() {
-            return Debug::fmt(&self.inner, f);
+            return Debug::fmt(&self.report.inner, f);
         }
 
+        // NOTE: `Debug` is used for any formatting, not just reporting errors
+        // returned from `main`, so it never saves crash report
         let config = GLOBAL_SETTINGS.get_or_init(Config::new);
+        Display::fmt(&config.bundle(self), f)
+    }
+}
+
+impl Display for Bundle<'_, &Terminator> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.config.format {
+            Format::Pretty => self.pretty(f),
+            Format::Compact => self.compact(f),
+            #[cfg(feature = "json")]
+            Format::Json => self.json(f),
+        }
+    }
+}
 
-        for (index, error) in self.chain().enumerate() {
-            write!(f, "\n{:>4}: {}", index, config.theme.error.style(error))?;
+impl Bundle<'_, &Terminator> {
+    fn chain(&self, f: &mut Formatter<'_>) -> Result {
+        let config = self.config;
+        let chain = self.data.chain();
+        if config.dedup && config.selected_verbosity() != Verbosity::Full {
+            let messages = dedup::deduplicate(chain.map(ToString::to_string).collect());
+            self.messages(f, messages.iter())
+        } else {
+            self.messages(f, chain)
         }
+    }
 
-        write!(Indent::double(f), "\n\n{}", config.bundle(self.backtrace()))
+    fn messages<T: Display>(
+        &self,
+        f: &mut Formatter<'_>,
+        messages: impl Iterator<Item = T>,
+    ) -> Result {
+        let config = self.config;
+        for (index, message) in messages.enumerate() {
+            let message = config.theme.error.style(message);
+            let indentation = if config.format == Format::Compact {
+                if index > 0 {
+                    f.write_str(": ")?;
+                }
+                "  "
+            } else {
+                write!(f, "\n{index:>4}: ")?;
+                "      "
+            };
+            write!(Indent::hanging(f, indentation), "{message}")?;
+        }
+        Ok(())
+    }
+
+    fn pretty(&self, f: &mut Formatter<'_>) -> Result {
+        let config = self.config;
+        let error = self.data;
+
+        self.chain(f)?;
+
+        let location = Location::derived(error.location());
+        write!(f, "\n\nLocation: {}", config.bundle(&location))?;
+
+        if !error.report.help.is_empty() {
+            f.write_char('\n')?;
+        }
+        for help in &error.report.help {
+            write!(f, "\n{}", config.bundle(help))?;
+        }
+
+        if !error.report.trace.0.is_empty() {
+            write!(
+                Indent::double(f),
+                "\n\n{}",
+                config.bundle(&error.report.trace)
+            )?;
+        }
+
+        #[cfg(feature = "tracing")]
+        if let Some(spantrace) = error.spantrace() {
+            write!(Indent::double(f), "\n\n{}", config.bundle(spantrace))?;
+        }
+
+        write!(Indent::double(f), "\n\n{}", config.bundle(error.frames()))
+    }
+
+    fn compact(&self, f: &mut Formatter<'_>) -> Result {
+        let config = self.config;
+        let error = self.data;
+
+        self.chain(f)?;
+
+        let location = Location::derived(error.location());
+        write!(f, "\nLocation: {}", config.bundle(&location))?;
+
+        for help in &error.report.help {
+            write!(f, "\n{}", config.bundle(help))?;
+        }
+
+        if !error.report.trace.0.is_empty() {
+            write!(
+                Indent::double(f),
+                "\n\n{}",
+                config.bundle(&error.report.trace)
+            )?;
+        }
+
+        #[cfg(feature = "tracing")]
+        if let Some(spantrace) = error.spantrace() {
+            write!(Indent::double(f), "\n\n{}", config.bundle(spantrace))?;
+        }
+
+        if let Some(frames) = error.frames() {
+            write!(Indent::double(f), "\n\n{}", config.bundle(frames))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Renders report of `error` using this [`Config`]
+    ///
+    /// Unlike [`Debug`] implementation of [`Terminator`], this does not depend on
+    /// globally installed [`Config`]. The report is not saved into crash report file.
+    #[must_use]
+    pub fn report<'a>(&'a self, error: &'a Terminator) -> impl Display + 'a {
+        self.bundle(error)
     }
 }
 
 impl Display for Terminator {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        writeln!(f, "Error: {self:?}")
+        let config = GLOBAL_SETTINGS.get_or_init(Config::new);
+        let report = config.bundle(self);
+
+        #[cfg(feature = "json")]
+        if config.format == Format::Json {
+            return writeln!(f, "{report}");
+        }
+
+        writeln!(f, "Error: {report}")
     }
 }
 
@@ -87,6 +423,7 @@ impl<E> From<E> for Terminator
 where
     E: Into<Inner>,
 {
+    #[track_caller]
     fn from(value: E) -> Self {
         Self::new(value.into())
     }
@@ -97,6 +434,7 @@ impl<E> From<E> for Terminator
 where
     E: Into<Inner>,
 {
+    #[track_caller]
     fn from(value: E) -> Self {
         Self::new(value.into())
     }
@@ -107,11 +445,29 @@ impl<E> From<E> for Terminator
 where
     E: Error + Send + Sync + 'static,
 {
+    #[track_caller]
     fn from(value: E) -> Self {
         Self::new(value.into())
     }
 }
 
+/// Error consisting only of displayable message
+pub(crate) struct Message<M>(pub M);
+
+impl<M: Display> Debug for Message<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<M: Display> Display for Message<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<M: Display> Error for Message<M> {}
+
 /// Error for when [`Config`] had already been installed
 #[derive(Debug)]
 pub struct InstallError;
@@ -123,3 +479,147 @@ impl Display for InstallError {
 }
 
 impl Error for InstallError {}
+
+/// Error with context over an I/O error, shared by tests
+#[cfg(test)]
+pub(crate) fn failure() -> Terminator {
+    use crate::Context;
+
+    Err::<(), _>(std::io::Error::other("root cause"))
+        .context("outer")
+        .unwrap_err()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::Context;
+    use crate::Theme;
+
+    #[test]
+    fn size() {
+        assert_eq!(size_of::<Terminator>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn downcast() {
+        let mut error = Err::<(), _>(io::Error::other("root cause"))
+            .context(io::Error::from(io::ErrorKind::TimedOut))
+            .context("outer")
+            .unwrap_err();
+        assert!(error.is::<io::Error>());
+        assert_eq!(
+            error.downcast_ref::<io::Error>().map(io::Error::kind),
+            Some(io::ErrorKind::TimedOut)
+        );
+        assert!(error.downcast_mut::<io::Error>().is_some());
+        assert!(error.downcast::<io::Error>().is_ok());
+
+        let error = Err::<(), _>(Terminator::msg("message"))
+            .context("outer")
+            .unwrap_err();
+        assert!(!error.is::<io::Error>());
+        assert!(error.downcast::<io::Error>().is_err());
+    }
+
+    #[test]
+    fn from_boxed() {
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(io::Error::other("boxed"));
+        let error = Err::<(), _>(Terminator::from_boxed(boxed))
+            .context("outer")
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<io::Error>().map(ToString::to_string),
+            Some("boxed".to_owned())
+        );
+        let chain: Vec<_> = error.chain().map(ToString::to_string).collect();
+        assert_eq!(chain, ["outer", "boxed"]);
+    }
+
+    #[test]
+    fn downcast_source() {
+        #[derive(Debug)]
+        struct Outer(io::Error);
+
+        impl Display for Outer {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("outer")
+            }
+        }
+
+        impl Error for Outer {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let error = Terminator::from(Outer(io::Error::other("root cause")));
+        assert!(error.is::<io::Error>());
+        assert!(error.downcast::<io::Error>().is_err());
+    }
+
+    #[test]
+    fn compact() {
+        let error = Err::<(), _>(failure()).context("top").unwrap_err();
+        let config = Config::blank()
+            .theme(Theme::blank())
+            .format(Format::Compact);
+        let rendered = config.report(&error).to_string();
+        assert_eq!(rendered.lines().next(), Some("top: outer: root cause"));
+        let location = Location::derived(error.location());
+        let location = format!("Location: {}", config.bundle(&location));
+        assert_eq!(rendered.lines().nth(1), Some(location.as_str()));
+
+        let error = Err::<(), _>(io::Error::other("root\ncause"))
+            .context("outer")
+            .unwrap_err();
+        let rendered = config.report(&error).to_string();
+        assert!(rendered.starts_with("outer: root\n  cause\nLocation: "));
+    }
+
+    // NOTE: `eyre` captures spantrace only with hook installed globally
+    #[cfg(all(feature = "tracing", not(feature = "eyre")))]
+    #[test]
+    fn spantrace() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let subscriber =
+            tracing_subscriber::Registry::default().with(tracing_error::ErrorLayer::default());
+        let error = tracing::subscriber::with_default(subscriber, || {
+            let _span = tracing::info_span!("request", id = 7).entered();
+            failure()
+        });
+        let config = Config::blank().theme(Theme::blank());
+        let rendered = config.bundle(&error).to_string();
+        let spantrace = &rendered[rendered.find(" SPANTRACE ").unwrap()..];
+        assert!(spantrace.contains("0: terminator::error::tests::request with id=7\n      at "));
+    }
+
+    #[test]
+    fn multiline() {
+        let error = Err::<(), _>(io::Error::other("root\ncause"))
+            .context("outer\n\nmessage")
+            .unwrap_err();
+        let config = Config::blank().theme(Theme::blank());
+        let rendered = config.bundle(&error).to_string();
+        assert!(rendered.starts_with("\n   0: outer\n\n      message\n   1: root\n      cause\n"));
+    }
+
+    #[test]
+    fn location() {
+        fn convert() -> std::result::Result<(), Terminator> {
+            Err(io::Error::other("io"))?
+        }
+
+        let error = Terminator::msg("message");
+        assert_eq!(error.location().line(), line!() - 1);
+        let error = Err::<(), _>(io::Error::other("io"))
+            .context("outer")
+            .unwrap_err();
+        assert_eq!(error.location().line(), line!() - 2);
+        assert_eq!(error.location().file(), file!());
+        assert_eq!(convert().unwrap_err().location().line(), line!() - 10);
+    }
+}
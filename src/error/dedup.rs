@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+/// Separators between message and text of its source
+const SEPARATORS: [&str; 4] = [": ", " - ", "; ", ", "];
+
+/// Brackets enclosing text of source within message
+const BRACKETS: [(char, char); 2] = [('(', ')'), ('[', ']')];
+
+/// Removes text repeated from sources in messages of an error chain
+///
+/// Message ending with a separator followed by its source is trimmed, source
+/// enclosed in brackets anywhere in message is removed and message equal to its
+/// source is dropped.
+pub fn deduplicate(messages: Vec<String>) -> Vec<String> {
+    let mut result = Vec::with_capacity(messages.len());
+    let mut messages = messages.into_iter().peekable();
+
+    while let Some(mut message) = messages.next() {
+        let Some(source) = messages.peek().filter(|source| !source.is_empty()) else {
+            result.push(message);
+            continue;
+        };
+
+        if message == *source {
+            continue;
+        }
+        if let Some(trimmed) = message.strip_suffix(source.as_str()).and_then(|trimmed| {
+            SEPARATORS
+                .iter()
+                .find_map(|separator| trimmed.strip_suffix(separator))
+        }) {
+            let length = trimmed.trim_end().len();
+            if length == 0 {
+                continue;
+            }
+            message.truncate(length);
+        } else if let Some(range) = enclosed(&message, source) {
+            message.replace_range(range, "");
+            if message.trim().is_empty() {
+                continue;
+            }
+        }
+
+        result.push(message);
+    }
+
+    result
+}
+
+/// Finds source enclosed in brackets together with one adjacent space
+fn enclosed(message: &str, source: &str) -> Option<Range<usize>> {
+    BRACKETS.iter().find_map(|(open, close)| {
+        let enclosed = format!("{open}{source}{close}");
+        let start = message.find(&enclosed)?;
+        let end = start + enclosed.len();
+        if message[..start].ends_with(' ') {
+            Some(start - 1..end)
+        } else if message[end..].starts_with(' ') {
+            Some(start..end + 1)
+        } else {
+            Some(start..end)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dedup(messages: &[&str]) -> Vec<String> {
+        deduplicate(messages.iter().copied().map(String::from).collect())
+    }
+
+    #[test]
+    fn suffix() {
+        assert_eq!(
+            dedup(&["failed to read config: No such file", "No such file"]),
+            ["failed to read config", "No such file"]
+        );
+    }
+
+    #[test]
+    fn equal() {
+        assert_eq!(dedup(&["outer", "inner", "inner"]), ["outer", "inner"]);
+    }
+
+    #[test]
+    fn boundary() {
+        assert_eq!(
+            dedup(&["failed to parse: invalid input", "input"]),
+            ["failed to parse: invalid input", "input"]
+        );
+        assert_eq!(
+            dedup(&["parse error at line 3", "error"]),
+            ["parse error at line 3", "error"]
+        );
+        assert_eq!(
+            dedup(&["failed (No such file: config) at startup", "No such file"]),
+            ["failed (No such file: config) at startup", "No such file"]
+        );
+    }
+
+    #[test]
+    fn enclosed() {
+        assert_eq!(
+            dedup(&["failed (No such file) at startup", "No such file"]),
+            ["failed at startup", "No such file"]
+        );
+        assert_eq!(
+            dedup(&["[timed out] request failed", "timed out"]),
+            ["request failed", "timed out"]
+        );
+        assert_eq!(dedup(&["(inner)", "inner"]), ["inner"]);
+    }
+
+    #[test]
+    fn unrelated() {
+        assert_eq!(dedup(&["outer", "", "inner"]), ["outer", "", "inner"]);
+    }
+}
@@ -1,17 +1,23 @@
-use std::backtrace::Backtrace;
 use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result;
+use std::panic::Location;
 
 use eyre::EyreHandler;
+#[cfg(feature = "tracing")]
+use tracing_error::SpanTrace;
 
+use crate::backtrace::Capture;
 use crate::Config;
 use crate::Verbosity;
 
 pub struct BacktraceHandler {
-    backtrace: Option<Backtrace>,
+    backtrace: Option<Capture>,
+    location: Option<&'static Location<'static>>,
+    #[cfg(feature = "tracing")]
+    spantrace: SpanTrace,
 }
 
 type Handler = dyn Fn(&(dyn Error + 'static)) -> Box<dyn EyreHandler> + Sync + Send + 'static;
@@ -20,16 +26,28 @@ impl Config {
     pub(crate) fn eyre_hook(&'static self) -> Box<Handler> {
         Box::new(move |_| {
             Box::new(BacktraceHandler {
-                backtrace: (self.error != Verbosity::Minimal).then(Backtrace::force_capture),
+                backtrace: (self.error != Verbosity::Minimal).then(Capture::new),
+                location: None,
+                #[cfg(feature = "tracing")]
+                spantrace: SpanTrace::capture(),
             })
         })
     }
 }
 
 impl BacktraceHandler {
-    pub fn backtrace(&self) -> Option<&Backtrace> {
+    pub fn backtrace(&self) -> Option<&Capture> {
         self.backtrace.as_ref()
     }
+
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn spantrace(&self) -> &SpanTrace {
+        &self.spantrace
+    }
 }
 
 impl EyreHandler for BacktraceHandler {
@@ -40,4 +58,8 @@ impl EyreHandler for BacktraceHandler {
     fn display(&self, error: &(dyn Error + 'static), f: &mut Formatter<'_>) -> Result {
         Display::fmt(error, f)
     }
+
+    fn track_caller(&mut self, location: &'static Location<'static>) {
+        self.location = Some(location);
+    }
 }
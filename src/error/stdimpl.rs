@@ -1,10 +1,14 @@
-use std::backtrace::Backtrace;
+use std::any::Any;
 use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result;
 
+#[cfg(feature = "tracing")]
+use tracing_error::SpanTrace;
+
+use crate::backtrace::Capture;
 use crate::Config;
 use crate::Verbosity;
 use crate::GLOBAL_SETTINGS;
@@ -28,26 +32,116 @@ impl<'a> Iterator for Chain<'a> {
     }
 }
 
-// HACK: can't be declared directly, see three lines below. In order to not misuse
-// the helper, implement traits and methods directly on this (ie. no generics).
-pub type DynError = ErrorUnsizingHelper<dyn Error + Send + Sync + 'static>;
+type BoxedError = Box<dyn Error + Send + Sync + 'static>;
 
-// NOTE: `DynError` has to have generic helper (not directly error = dyn Error + 'static),
-// because it is almost impossible to create instance of unsized structs directly.
-pub struct ErrorUnsizingHelper<E: ?Sized> {
-    backtrace: Option<Backtrace>,
-    error: E,
+// NOTE: error is boxed separately (instead of unsizing the whole struct), so that
+// it can be moved out when wrapping it in context.
+pub struct DynError {
+    backtrace: Option<Capture>,
+    #[cfg(feature = "tracing")]
+    spantrace: SpanTrace,
+    error: BoxedError,
 }
 
 impl DynError {
-    pub fn backtrace(&self) -> Option<&Backtrace> {
+    pub fn new(error: BoxedError) -> Self {
+        let backtrace = (GLOBAL_SETTINGS.get().map(Config::selected_verbosity)
+            != Some(Verbosity::Minimal))
+        .then(Capture::new);
+        Self {
+            backtrace,
+            #[cfg(feature = "tracing")]
+            spantrace: SpanTrace::capture(),
+            error,
+        }
+    }
+
+    pub fn backtrace(&self) -> Option<&Capture> {
         self.backtrace.as_ref()
     }
-}
 
-impl DynError {
+    #[cfg(feature = "tracing")]
+    pub fn spantrace(&self) -> &SpanTrace {
+        &self.spantrace
+    }
+
     pub fn chain(&self) -> impl Iterator<Item = &(dyn Error + 'static)> {
-        Chain::new(&self.error)
+        Chain::new(&*self.error)
+    }
+
+    pub fn context<C>(mut self, context: C) -> Self
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        let context = Box::new(context);
+        self.error = Box::new(ContextError {
+            context,
+            error: self.error,
+        });
+        self
+    }
+
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let mut error = &*self.error;
+        loop {
+            if let Some(error) = error.downcast_ref::<E>() {
+                return Some(error);
+            }
+            let layer = error.downcast_ref::<ContextError>()?;
+            if let Some(context) = (*layer.context).as_any().downcast_ref::<E>() {
+                return Some(context);
+            }
+            error = &*layer.error;
+        }
+    }
+
+    pub fn downcast_mut<E>(&mut self) -> Option<&mut E>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let mut error = &mut *self.error;
+        loop {
+            if error.is::<E>() {
+                return error.downcast_mut::<E>();
+            }
+            let layer = error.downcast_mut::<ContextError>()?;
+            if (*layer.context).as_any().is::<E>() {
+                return (*layer.context).as_any_mut().downcast_mut::<E>();
+            }
+            error = &mut *layer.error;
+        }
+    }
+
+    // NOTE: only moved in and out of box of `Terminator`, so size doesn't matter
+    #[allow(clippy::result_large_err)]
+    pub fn downcast<E>(self) -> std::result::Result<E, Self>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        if self.downcast_ref::<E>().is_none() {
+            return Err(self);
+        }
+        let mut error = self.error;
+        loop {
+            error = match error.downcast::<E>() {
+                Ok(error) => return Ok(*error),
+                Err(error) => error,
+            };
+            let layer = error
+                .downcast::<ContextError>()
+                .expect("`ContextError` was checked to contain `E`");
+            let ContextError {
+                context,
+                error: source,
+            } = *layer;
+            if let Ok(context) = context.into_any().downcast::<E>() {
+                return Ok(*context);
+            }
+            error = source;
+        }
     }
 }
 
@@ -63,26 +157,65 @@ impl Display for DynError {
     }
 }
 
-impl Error for DynError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.error.source()
+impl<E> From<E> for DynError
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn from(value: E) -> Self {
+        DynError::new(Box::new(value))
     }
 }
 
-impl<E> From<E> for Box<DynError>
+/// Type erased context, which can still be downcast to concrete type
+///
+/// NOTE: `Box<dyn Contextual>` implements this trait as well, so methods have
+/// to be called on dereferenced box.
+trait Contextual: Display + Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<C> Contextual for C
 where
-    E: Error + Send + Sync + 'static,
+    C: Display + Send + Sync + 'static,
 {
-    fn from(value: E) -> Self {
-        let backtrace = GLOBAL_SETTINGS
-            .get()
-            .map(Config::selected_verbosity)
-            .map_or(true, |verbosity| verbosity != Verbosity::Minimal)
-            .then(Backtrace::force_capture);
-        let error = ErrorUnsizingHelper {
-            backtrace,
-            error: value,
-        };
-        Box::new(error)
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Layer of context on top of underlying error
+struct ContextError {
+    context: Box<dyn Contextual>,
+    error: BoxedError,
+}
+
+impl Debug for ContextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Error")
+            .field("context", &format_args!("{}", self.context))
+            .field("source", &self.error)
+            .finish()
+    }
+}
+
+impl Display for ContextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.context, f)
+    }
+}
+
+impl Error for ContextError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
     }
 }
@@ -0,0 +1,148 @@
+use std::error::Error;
+use std::io::stderr;
+use std::io::IsTerminal;
+use std::io::LineWriter;
+use std::io::Write;
+use std::process::ExitCode;
+use std::process::Termination;
+
+use crate::link;
+use crate::Config;
+use crate::Terminator;
+use crate::GLOBAL_SETTINGS;
+
+/// Callback mapping [`Terminator`] to process exit code
+pub(crate) type ExitCodeMapper = dyn Fn(&Terminator) -> Option<u8> + Send + Sync + 'static;
+
+impl Config {
+    /// Map errors of type `E` to process exit code
+    ///
+    /// Whole chain of [`Terminator`] is searched (see [`Terminator::is`]). Mappings
+    /// are tried in order they were registered, code set explicitly with
+    /// [`Terminator::with_exit_code`] takes precedence. Code `0` would report
+    /// success, so `1` is used instead.
+    #[inline]
+    pub fn exit_code<E>(mut self, code: u8) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let code = code.max(1);
+        self.codes
+            .push(Box::new(move |error| error.is::<E>().then_some(code)));
+        self
+    }
+
+    pub(crate) fn resolve_exit_code(&self, error: &Terminator) -> u8 {
+        error
+            .report
+            .exit
+            .or_else(|| self.codes.iter().find_map(|mapper| mapper(error)))
+            .unwrap_or(1)
+    }
+}
+
+impl Terminator {
+    /// Set exit code used when this error terminates the process
+    ///
+    /// Code `0` would report success, so `1` is used instead.
+    #[must_use]
+    pub fn with_exit_code(mut self, code: u8) -> Self {
+        self.report.exit = Some(code.max(1));
+        self
+    }
+
+    /// Explicitly set exit code
+    #[must_use]
+    pub fn exit_code(&self) -> Option<u8> {
+        self.report.exit
+    }
+}
+
+/// Return value of main function which exits with code based on [`Terminator`]
+///
+/// Errors are printed the same way as when [`Terminator`] is returned from main,
+/// but the process exits with code set by [`Terminator::with_exit_code`] or
+/// [`Config::exit_code`] instead of always using `1`.
+///
+/// ```
+/// fn run() -> Result<(), terminator::Terminator> {
+///     Ok(())
+/// }
+///
+/// fn main() -> terminator::Outcome {
+///     run().into()
+/// }
+/// ```
+#[must_use]
+pub struct Outcome<T = ()>(pub Result<T, Terminator>);
+
+impl<T, E> From<Result<T, E>> for Outcome<T>
+where
+    E: Into<Terminator>,
+{
+    fn from(value: Result<T, E>) -> Self {
+        Self(value.map_err(Into::into))
+    }
+}
+
+impl<T: Termination> Termination for Outcome<T> {
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(value) => value.report(),
+            Err(error) => {
+                let config = GLOBAL_SETTINGS.get_or_init(Config::new);
+                let mut report = String::new();
+                let terminal = stderr().is_terminal();
+                _ = link::destination(terminal, || config.emit(&mut report, "error", &error));
+                _ = LineWriter::new(stderr()).write_all(report.as_bytes());
+                ExitCode::from(config.resolve_exit_code(&error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::io;
+
+    use super::*;
+    use crate::error::failure;
+
+    #[derive(Debug)]
+    struct Usage;
+
+    impl fmt::Display for Usage {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("invalid usage")
+        }
+    }
+
+    impl Error for Usage {}
+
+    #[test]
+    fn resolution() {
+        let config = Config::blank()
+            .exit_code::<Usage>(2)
+            .exit_code::<io::Error>(74);
+
+        let error = failure();
+        assert_eq!(config.resolve_exit_code(&error), 74);
+        assert_eq!(config.resolve_exit_code(&error.with_exit_code(3)), 3);
+        assert_eq!(config.resolve_exit_code(&Terminator::from(Usage)), 2);
+        assert_eq!(config.resolve_exit_code(&Terminator::msg("other")), 1);
+        assert_eq!(config.resolve_exit_code(&failure().with_exit_code(0)), 1);
+
+        let config = Config::blank().exit_code::<io::Error>(0);
+        assert_eq!(config.resolve_exit_code(&failure()), 1);
+    }
+
+    #[test]
+    fn report() {
+        assert_eq!(Outcome(Ok(())).report(), ExitCode::SUCCESS);
+        let error = failure().with_exit_code(3);
+        assert_eq!(Outcome::<()>(Err(error)).report(), ExitCode::from(3));
+        let error = failure().with_exit_code(0);
+        assert_eq!(Outcome::<()>(Err(error)).report(), ExitCode::FAILURE);
+    }
+}
@@ -0,0 +1,41 @@
+use std::env;
+use std::ffi::OsStr;
+
+use crate::consts::FORMAT;
+
+/// Setting for layout of printed errors
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// Error chain is printed as numbered list followed by sections and backtrace
+    #[default]
+    Pretty,
+    /// Error chain is printed on single line followed by location, backtrace is
+    /// shown only if captured
+    Compact,
+    /// Errors and panics are printed as single line JSON objects
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    Json,
+}
+
+impl Format {
+    const fn decode(thing: &[u8]) -> Option<Self> {
+        match thing {
+            b"pretty" => Some(Format::Pretty),
+            b"compact" => Some(Format::Compact),
+            #[cfg(feature = "json")]
+            b"json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    /// Retrieves [`Format`] from `TERMINATOR_FORMAT` environment variable
+    #[must_use]
+    pub fn env() -> Option<Self> {
+        env::var_os(FORMAT)
+            .as_deref()
+            .map(OsStr::as_encoded_bytes)
+            .and_then(Self::decode)
+    }
+}
@@ -0,0 +1,173 @@
+use std::env;
+use std::fmt;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+
+use crate::consts::BACKTRACE;
+use crate::crash::save;
+use crate::Config;
+
+/// Information about application shown to users in friendly panic message
+///
+/// Use [`metadata!`](crate::metadata) to fill it from `Cargo.toml` of your crate.
+#[derive(Debug, Clone)]
+pub struct Metadata {
+    name: &'static str,
+    version: &'static str,
+    authors: &'static str,
+    homepage: &'static str,
+    release: bool,
+}
+
+impl Metadata {
+    /// Creates new [`Metadata`] for application with given name and version
+    #[inline]
+    #[must_use]
+    pub const fn new(name: &'static str, version: &'static str) -> Self {
+        Self {
+            name,
+            version,
+            authors: "",
+            homepage: "",
+            release: false,
+        }
+    }
+
+    /// Set authors of the application, separated by `:` as in `CARGO_PKG_AUTHORS`
+    #[inline]
+    #[must_use]
+    pub const fn authors(mut self, authors: &'static str) -> Self {
+        self.authors = authors;
+        self
+    }
+
+    /// Set homepage of the application
+    #[inline]
+    #[must_use]
+    pub const fn homepage(mut self, homepage: &'static str) -> Self {
+        self.homepage = homepage;
+        self
+    }
+
+    /// Mark application as built in release mode, which enables friendly panics
+    #[inline]
+    #[must_use]
+    pub const fn release(mut self, release: bool) -> Self {
+        self.release = release;
+        self
+    }
+}
+
+impl Config {
+    /// Set [`Metadata`] of the application used in friendly panic message
+    ///
+    /// Friendly panics are enabled when [`Metadata`] comes from release build,
+    /// unless `RUST_BACKTRACE` is set. Use [`Config::friendly`] to override it.
+    #[inline]
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.friendly = metadata.release && env::var_os(BACKTRACE).is_none();
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Replace panic report with short apology pointing to crash report file
+    ///
+    /// This only takes effect when [`Metadata`] is set. Full report is written
+    /// into [`Config::crash_dir`], or temporary directory if it is not set.
+    #[inline]
+    pub fn friendly(mut self, enabled: bool) -> Self {
+        self.friendly = enabled;
+        self
+    }
+
+    /// Writes friendly panic message if friendly panics are enabled
+    pub(crate) fn friendly_panic(
+        &self,
+        f: &mut dyn fmt::Write,
+        info: &PanicHookInfo<'_>,
+    ) -> Option<fmt::Result> {
+        let metadata = self.metadata.as_ref().filter(|_| self.friendly)?;
+
+        #[cfg(feature = "json")]
+        if self.format == crate::Format::Json {
+            return None;
+        }
+
+        let dir = self.crash_dir.clone().unwrap_or_else(env::temp_dir);
+        let report = self.bundle(info).to_string();
+        Some(write_friendly(
+            f,
+            self,
+            metadata,
+            save(&dir, "panic", &report).ok(),
+        ))
+    }
+}
+
+fn write_friendly(
+    f: &mut dyn fmt::Write,
+    config: &Config,
+    metadata: &Metadata,
+    path: Option<PathBuf>,
+) -> fmt::Result {
+    let theme = &config.theme;
+    let Metadata { name, version, .. } = metadata;
+
+    writeln!(f, "{}", theme.header.style("Well, this is embarrassing."))?;
+    writeln!(f)?;
+    writeln!(
+        f,
+        "{} had a problem and crashed. To help us diagnose the problem you can send us a crash report.",
+        theme.message.style(format_args!("{name} {version}")),
+    )?;
+    writeln!(f)?;
+    match path {
+        Some(path) => writeln!(
+            f,
+            "We have generated a report file at {}. Submit an issue or email with the subject of \"{name} Crash Report\" and include the report as an attachment.",
+            theme.file.style(path.display()),
+        )?,
+        None => writeln!(
+            f,
+            "We could not generate a report file. Submit an issue or email with the subject of \"{name} Crash Report\" and describe what you were doing.",
+        )?,
+    }
+
+    if !metadata.homepage.is_empty() || !metadata.authors.is_empty() {
+        writeln!(f)?;
+    }
+    if !metadata.homepage.is_empty() {
+        writeln!(f, "- Homepage: {}", metadata.homepage)?;
+    }
+    if !metadata.authors.is_empty() {
+        let authors = metadata.authors.split(':').collect::<Vec<_>>();
+        writeln!(f, "- Authors: {}", authors.join(", "))?;
+    }
+
+    writeln!(f)?;
+    writeln!(
+        f,
+        "We take privacy seriously, and do not perform any automated error collection. In order to improve the software, we rely on people to submit reports."
+    )?;
+    writeln!(f)?;
+    writeln!(f, "Thank you kindly!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Theme;
+
+    #[test]
+    fn message() {
+        let config = Config::blank().theme(Theme::blank());
+        let metadata = Metadata::new("app", "1.0.0").authors("Alice:Bob");
+        let mut output = String::new();
+        write_friendly(&mut output, &config, &metadata, Some("crash.log".into())).unwrap();
+
+        assert!(output.contains("app 1.0.0 had a problem and crashed."));
+        assert!(output.contains("report file at crash.log."));
+        assert!(output.contains("- Authors: Alice, Bob\n"));
+        assert!(!output.contains("Homepage"));
+    }
+}
@@ -25,6 +25,15 @@ where
     pub fn double(writer: &'a mut F) -> Self {
         Self::new(writer, "  ")
     }
+
+    /// Indents every line except the first one
+    pub fn hanging(writer: &'a mut F, indentation: &'a str) -> Self {
+        Self {
+            indentation,
+            writer,
+            requires: false,
+        }
+    }
 }
 
 impl<F> Write for Indent<'_, F>
@@ -0,0 +1,176 @@
+use std::fmt::Formatter;
+use std::fmt::Result;
+use std::panic::PanicHookInfo;
+
+use serde::Serialize;
+
+use crate::backtrace::Capture;
+use crate::config::Bundle;
+use crate::location::Location;
+use crate::panic::payload;
+use crate::section::Help;
+use crate::Config;
+use crate::Frame;
+use crate::Terminator;
+
+#[derive(Serialize)]
+struct JsonLocation<'a> {
+    file: &'a str,
+    line: u32,
+    column: Option<u32>,
+}
+
+impl<'a> From<&'a Location<'_>> for JsonLocation<'a> {
+    fn from(location: &'a Location<'_>) -> Self {
+        Self {
+            file: location.file(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonFrame<'a> {
+    index: usize,
+    name: Option<&'a str>,
+    file: Option<&'a str>,
+    line: Option<u32>,
+    column: Option<u32>,
+    address: Option<usize>,
+    inlined: bool,
+    dependency: bool,
+    filtered: bool,
+}
+
+#[derive(Serialize)]
+struct JsonSection {
+    kind: &'static str,
+    message: String,
+}
+
+impl From<&Help> for JsonSection {
+    fn from(help: &Help) -> Self {
+        let (kind, message) = match help {
+            Help::Note(message) => ("note", message),
+            Help::Suggestion(message) => ("suggestion", message),
+            Help::Warning(message) => ("warning", message),
+        };
+        let message = message.to_string();
+        Self { kind, message }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Report<'a> {
+    Error {
+        chain: Vec<String>,
+        location: JsonLocation<'a>,
+        return_trace: Vec<JsonLocation<'a>>,
+        sections: Vec<JsonSection>,
+        backtrace: Option<Vec<JsonFrame<'a>>>,
+    },
+    Panic {
+        message: &'a str,
+        location: Option<JsonLocation<'a>>,
+        backtrace: Vec<JsonFrame<'a>>,
+    },
+}
+
+impl Report<'_> {
+    fn write(&self, f: &mut Formatter<'_>) -> Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+/// Converts frames to their JSON representation, marking those hidden by filters
+fn frames<'a>(config: &Config, frames: &'a [Frame<'a>]) -> Vec<JsonFrame<'a>> {
+    let hidden = config.hidden_frames(frames);
+    frames
+        .iter()
+        .zip(hidden)
+        .map(|(frame, filtered)| JsonFrame {
+            index: frame.index(),
+            name: frame.name(),
+            file: frame.location().map(Location::file),
+            line: frame.location().map(Location::line),
+            column: frame.location().and_then(Location::column),
+            address: frame.address(),
+            inlined: frame.is_inlined(),
+            dependency: frame.is_dependency_code(),
+            filtered,
+        })
+        .collect()
+}
+
+impl Bundle<'_, &Terminator> {
+    pub(crate) fn json(&self, f: &mut Formatter<'_>) -> Result {
+        let error = self.data;
+        let location = Location::derived(error.location());
+        let trace: Vec<_> = error
+            .return_trace()
+            .iter()
+            .map(|location| Location::derived(location))
+            .collect();
+        Report::Error {
+            chain: error.chain().map(ToString::to_string).collect(),
+            location: JsonLocation::from(&location),
+            return_trace: trace.iter().map(JsonLocation::from).collect(),
+            sections: error.report.help.iter().map(JsonSection::from).collect(),
+            backtrace: error.frames().map(|parsed| frames(self.config, parsed)),
+        }
+        .write(f)
+    }
+}
+
+impl Bundle<'_, &PanicHookInfo<'_>> {
+    pub(crate) fn json(&self, f: &mut Formatter<'_>) -> Result {
+        let location = self.data.location().map(Location::derived);
+        let backtrace = Capture::new();
+        Report::Panic {
+            message: payload(self.data),
+            location: location.as_ref().map(JsonLocation::from),
+            backtrace: frames(self.config, backtrace.frames()),
+        }
+        .write(f)?;
+        f.write_str("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::failure;
+    use crate::Format;
+    use crate::Section;
+
+    #[test]
+    fn error() {
+        let error = Err::<(), _>(failure()).note("note").unwrap_err();
+        let config = Config::blank().format(Format::Json);
+        let rendered = config.bundle(&error).to_string();
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["kind"], "error");
+        assert_eq!(value["chain"], serde_json::json!(["outer", "root cause"]));
+        assert_eq!(value["location"]["line"], error.location().line());
+        assert_eq!(
+            value["sections"],
+            serde_json::json!([{ "kind": "note", "message": "note" }])
+        );
+    }
+
+    #[test]
+    fn filtered() {
+        let text = "   0: first\n   1: second\n   2: third\n";
+        let parsed = crate::ParsedBacktrace::parse(text).unwrap();
+        let config =
+            Config::blank().filter(Box::new(|frames| frames.retain(|frame| frame.index() != 1)));
+        let filtered: Vec<_> = frames(&config, parsed.frames())
+            .iter()
+            .map(|frame| frame.filtered)
+            .collect();
+        assert_eq!(filtered, [false, true, false]);
+    }
+}
@@ -11,26 +11,47 @@ compile_error!(r#"Features `anyhow` and `eyre` are mutualy exclusive. Use featur
 
 use std::sync::OnceLock;
 
+pub use backtrace::BacktraceError;
 pub use backtrace::Frame;
 pub use backtrace::FrameFilter;
+pub use backtrace::ParseDiagnostic;
+pub use backtrace::ParsedBacktrace;
 pub use config::Config;
+pub use context::Context;
 pub use error::InstallError;
 pub use error::Terminator;
+pub use exit::Outcome;
+pub use format::Format;
+pub use friendly::Metadata;
 pub use location::Location;
+pub use section::Section;
 pub use theme::Color;
 pub use theme::Effect;
 pub use theme::Style;
 pub use theme::Theme;
+pub use trace::Propagate;
 pub use verbosity::Verbosity;
 
 mod backtrace;
 mod config;
 mod consts;
+mod context;
+mod crash;
 mod error;
+mod exit;
+mod format;
+mod friendly;
 mod indent;
+#[cfg(feature = "json")]
+mod json;
+mod link;
 mod location;
+mod macros;
 mod panic;
+mod section;
+mod snippet;
 mod theme;
+mod trace;
 mod verbosity;
 
 macro_rules! cfg_compat {
@@ -49,4 +70,10 @@ cfg_compat!(
     pub use compat::ErrorCompat;
 );
 
+#[cfg(feature = "cli")]
+mod prettify;
+#[cfg(feature = "cli")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cli")))]
+pub use prettify::Prettifier;
+
 static GLOBAL_SETTINGS: OnceLock<Config> = OnceLock::new();
@@ -0,0 +1,257 @@
+use std::cell::Cell;
+use std::env;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::consts::LINKS;
+use crate::location::Location;
+use crate::Config;
+
+impl Config {
+    /// Render locations as terminal hyperlinks pointing to `template`
+    ///
+    /// Placeholders `{path}`, `{line}` and `{col}` in `template` are replaced
+    /// with percent-encoded absolute path, line and column of the location,
+    /// e.g. `file://{path}` or `vscode://file/{path}:{line}:{col}`. Relative
+    /// paths are resolved against working directory at the time of this call.
+    /// [`Config::new`] retrieves the template from `TERMINATOR_LINKS`
+    /// environment variable.
+    ///
+    /// Links are emitted only into standard error by panic hook and
+    /// [`crate::Outcome`], when it is a terminal, `TERM` is not `dumb`,
+    /// `NO_COLOR` is not set and [`crate::Theme`] is not blank. Destination of
+    /// reports rendered by [`Config::report`], [`Config::write_panic`] or by
+    /// formatting [`crate::Terminator`] is unknown, so they never contain links.
+    #[inline]
+    pub fn links(mut self, template: impl Into<String>) -> Self {
+        self.links = Some(Links::new(template.into()));
+        self
+    }
+
+    /// Annotate frames of standard library and crates.io dependencies with
+    /// links to their source code on the web
+    ///
+    /// Standard library links point to `rust-lang/rust` repository on GitHub
+    /// at the commit of the compiler, dependency links point to docs.rs.
+    #[inline]
+    pub fn web_links(mut self, enabled: bool) -> Self {
+        self.web_links = enabled;
+        self
+    }
+}
+
+/// Builds URL of source code at `location` on the web, if it is known
+pub(crate) fn web(location: &Location<'_>) -> Option<String> {
+    let file = location.file().replace('\\', "/");
+    let line = location.line();
+
+    if let Some(rest) = file.split_once("/rustc/").map(|(_, rest)| rest) {
+        let (commit, path) = rest.split_once('/')?;
+        let valid = commit.len() == 40 && commit.bytes().all(|byte| byte.is_ascii_hexdigit());
+        return valid
+            .then(|| format!("https://github.com/rust-lang/rust/blob/{commit}/{path}#L{line}"));
+    }
+
+    // NOTE: `<registry>/src/index.crates.io-<hash>/<name>-<version>/<path>`
+    let (index, rest) = file.split_once("/registry/src/")?.1.split_once('/')?;
+    if !index.starts_with("index.crates.io-") && !index.starts_with("github.com-") {
+        return None;
+    }
+    let (package, path) = rest.split_once('/')?;
+    let (name, version) = package
+        .match_indices('-')
+        .map(|(index, _)| (&package[..index], &package[index + 1..]))
+        .find(|(_, version)| is_version(version))?;
+    Some(format!(
+        "https://docs.rs/crate/{name}/{version}/source/{path}#L{line}"
+    ))
+}
+
+/// Checks whether `text` starts with `major.minor.patch` version
+fn is_version(text: &str) -> bool {
+    let core = text.split(['-', '+']).next().unwrap_or(text);
+    let parts: Vec<_> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()))
+}
+
+impl Config {
+    /// Gets links, if they can be displayed by output of reports
+    pub(crate) fn active_links(&self) -> Option<&Links> {
+        let links = self.links.as_ref()?;
+        if self.theme.is_blank() {
+            return None;
+        }
+        if !TERMINAL.get() {
+            return None;
+        }
+        let supported = *self.hyperlinks.get_or_init(|| {
+            let dumb = env::var_os("TERM").is_some_and(|term| term == "dumb");
+            let colorless = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            !dumb && !colorless
+        });
+        supported.then_some(links)
+    }
+}
+
+thread_local! {
+    /// Whether report rendered on this thread is written into a terminal
+    static TERMINAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Renders report with `render`, allowing links if it's written into a `terminal`
+pub(crate) fn destination<R>(terminal: bool, render: impl FnOnce() -> R) -> R {
+    let previous = TERMINAL.replace(terminal);
+    let result = render();
+    TERMINAL.set(previous);
+    result
+}
+
+/// Template of links along with directory, which relative paths are resolved against
+#[derive(Debug)]
+pub(crate) struct Links {
+    template: String,
+    current: Option<PathBuf>,
+}
+
+impl Links {
+    /// Creates [`Links`] resolving paths against current working directory
+    fn new(template: String) -> Self {
+        Self {
+            template,
+            current: env::current_dir().ok(),
+        }
+    }
+}
+
+/// Retrieves links template from environment
+pub(crate) fn env() -> Option<Links> {
+    env::var(LINKS)
+        .ok()
+        .filter(|template| !template.is_empty())
+        .map(Links::new)
+}
+
+/// Percent-encodes characters of `path`, which are not allowed in URL path
+fn encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~:".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// OSC 8 hyperlink wrapping `text`
+pub(crate) struct Link<'a, T> {
+    pub links: &'a Links,
+    pub location: &'a Location<'a>,
+    pub text: T,
+}
+
+impl<T: Display> Display for Link<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let Link {
+            links,
+            location,
+            ref text,
+        } = *self;
+
+        let file = Path::new(location.file());
+        let absolute = links
+            .current
+            .as_deref()
+            .filter(|_| file.is_relative())
+            .map(|dir| dir.join(file.strip_prefix(".").unwrap_or(file)));
+        let path = absolute.as_deref().unwrap_or(file).to_string_lossy();
+        let url = links
+            .template
+            .replace("{path}", &encode(&path))
+            .replace("{line}", &location.line().to_string())
+            .replace("{col}", &location.column().unwrap_or(1).to_string());
+
+        write!(f, "\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    #[test]
+    fn link() {
+        let location = Location {
+            file: Cow::Borrowed("/src/main.rs"),
+            line: 4,
+            column: Some(2),
+        };
+        let links = Links::new("vscode://file/{path}:{line}:{col}".to_owned());
+        let link = Link {
+            links: &links,
+            location: &location,
+            text: "main",
+        };
+        assert_eq!(
+            link.to_string(),
+            "\x1b]8;;vscode://file//src/main.rs:4:2\x1b\\main\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn destination() {
+        let config = Config::blank().links("file://{path}");
+        // NOTE: support of terminal is decided by environment of the test otherwise
+        config.hyperlinks.set(true).unwrap();
+        let template = || config.active_links().map(|links| links.template.as_str());
+        assert_eq!(template(), None);
+        assert_eq!(super::destination(true, template), Some("file://{path}"));
+        assert_eq!(template(), None);
+
+        let rendered = config.report(&crate::error::failure()).to_string();
+        assert!(!rendered.contains("\x1b]8;;"));
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(
+            encode("/home/my user/a#b%c/čaj.rs"),
+            "/home/my%20user/a%23b%25c/%C4%8Daj.rs"
+        );
+        assert_eq!(encode("C:/src/main.rs"), "C:/src/main.rs");
+    }
+
+    #[test]
+    fn web_links() {
+        let web = |file| {
+            web(&Location {
+                file: Cow::Borrowed(file),
+                line: 12,
+                column: None,
+            })
+        };
+
+        assert_eq!(
+            web("/rustc/59807616e1fa2540724bfbac14d7976d7e4a3860/library/core/src/panicking.rs")
+                .as_deref(),
+            Some("https://github.com/rust-lang/rust/blob/59807616e1fa2540724bfbac14d7976d7e4a3860/library/core/src/panicking.rs#L12")
+        );
+        assert_eq!(
+            web("/home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/sha-1-0.10.0-rc.1/src/lib.rs")
+                .as_deref(),
+            Some("https://docs.rs/crate/sha-1/0.10.0-rc.1/source/src/lib.rs#L12")
+        );
+        assert_eq!(web("/rustc/unknown/library/core/src/panicking.rs"), None);
+        assert_eq!(web("/home/user/project/src/main.rs"), None);
+    }
+}
@@ -1,17 +1,26 @@
 use std::borrow::Cow;
+use std::env;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result;
 use std::panic;
+use std::path::Path;
+use std::path::PathBuf;
 
 use crate::config::Bundle;
+use crate::consts::HOME;
+use crate::consts::RUST_PREFIX;
 use crate::consts::UNKNOWN;
+use crate::link::Link;
+use crate::Theme;
+use crate::Verbosity;
 
-/// Filename and line corresponding to source file
+/// Filename, line and optionally column corresponding to source file
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Location<'a> {
     pub(crate) file: Cow<'a, str>,
     pub(crate) line: u32,
+    pub(crate) column: Option<u32>,
 }
 
 impl<'a> Location<'a> {
@@ -19,6 +28,23 @@ impl<'a> Location<'a> {
         Self {
             file: Cow::Borrowed(location.file()),
             line: location.line(),
+            column: Some(location.column()),
+        }
+    }
+
+    pub(crate) fn borrowed(&self) -> Location<'_> {
+        Location {
+            file: Cow::Borrowed(&self.file),
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    pub(crate) fn into_owned(self) -> Location<'static> {
+        Location {
+            file: Cow::Owned(self.file.into_owned()),
+            line: self.line,
+            column: self.column,
         }
     }
 
@@ -35,18 +61,123 @@ impl<'a> Location<'a> {
     pub fn line(&self) -> u32 {
         self.line
     }
+
+    /// Gets column number, if known
+    #[must_use]
+    #[inline]
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
 }
 
 impl Display for Bundle<'_, &Location<'_>> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let theme = &self.config.theme;
+        let config = self.config;
+        let theme = &config.theme;
         let location = self.data;
-        let file = theme.file.style(location.file());
+        let (prefix, path) = match &config.shorten {
+            Some(roots) if config.selected_verbosity() != Verbosity::Full => {
+                shorten(location.file(), roots)
+            }
+            _ => ("", location.file()),
+        };
+        let text = Text {
+            theme,
+            prefix,
+            path,
+            location,
+        };
+        match config.active_links() {
+            Some(links) => write!(
+                f,
+                "{}",
+                Link {
+                    links,
+                    location,
+                    text,
+                }
+            ),
+            None => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Styled `file:line:col` text of location
+struct Text<'a> {
+    theme: &'a Theme,
+    prefix: &'a str,
+    path: &'a str,
+    location: &'a Location<'a>,
+}
+
+impl Display for Text<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let Text {
+            theme,
+            prefix,
+            path,
+            location,
+        } = *self;
         let line = theme.line.style(location.line());
-        write!(f, "{file}:{line}")
+        let file = format_args!("{prefix}{path}");
+        write!(f, "{}:{line}", theme.file.style(file))?;
+        if let Some(column) = location.column() {
+            write!(f, ":{}", theme.line.style(column))?;
+        }
+        Ok(())
     }
 }
 
+/// Directories stripped from shortened paths
+#[derive(Debug, Clone)]
+pub(crate) struct Roots {
+    current: Option<PathBuf>,
+    home: Option<PathBuf>,
+}
+
+impl Roots {
+    /// Retrieves current working and home directory
+    pub(crate) fn new() -> Self {
+        Self {
+            current: env::current_dir().ok(),
+            home: env::var_os(HOME).map(PathBuf::from),
+        }
+    }
+}
+
+/// Splits shortened `file` into replacement prefix and the rest of the path
+fn shorten<'a>(file: &'a str, roots: &Roots) -> (&'static str, &'a str) {
+    // NOTE: both `/rustc/<commit>/library/` and `rust-src` component
+    for marker in ["/rustc/", "/lib/rustlib/src/rust/"] {
+        if let Some(rest) = file
+            .find(marker)
+            .and_then(|index| file[index..].find("/library/").map(|next| index + next))
+        {
+            return (RUST_PREFIX, &file[rest + "/library/".len()..]);
+        }
+    }
+
+    // NOTE: skip the `index.crates.io-<hash>` component
+    if let Some(rest) = file
+        .find("/registry/src/")
+        .map(|index| &file[index + "/registry/src/".len()..])
+        .and_then(|rest| rest.split_once('/'))
+        .map(|(_, rest)| rest)
+    {
+        return ("", rest);
+    }
+
+    let path = Path::new(file);
+    let strip = |root: &Option<PathBuf>| path.strip_prefix(root.as_ref()?).ok()?.to_str();
+    if let Some(rest) = strip(&roots.current) {
+        return ("", rest);
+    }
+    if let Some(rest) = strip(&roots.home) {
+        return ("~/", rest);
+    }
+    ("", file)
+}
+
 impl Display for Bundle<'_, Option<&Location<'_>>> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         if let Some(location) = self.data {
@@ -56,3 +187,39 @@ impl Display for Bundle<'_, Option<&Location<'_>>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortening() {
+        let roots = Roots {
+            current: Some(PathBuf::from("/home/user/project")),
+            home: Some(PathBuf::from("/home/user")),
+        };
+        let shorten = |file| shorten(file, &roots);
+
+        let registry = "/home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/anyhow-1.0.81/src/error.rs";
+        assert_eq!(shorten(registry), ("", "anyhow-1.0.81/src/error.rs"));
+
+        let rustc =
+            "/rustc/7cf61ebde7b22796c69757901dd346d0fe70bd97/library/core/src/ops/function.rs";
+        assert_eq!(shorten(rustc), ("<rust>/", "core/src/ops/function.rs"));
+
+        let source =
+            "/home/user/.rustup/toolchains/stable/lib/rustlib/src/rust/library/std/src/rt.rs";
+        assert_eq!(shorten(source), ("<rust>/", "std/src/rt.rs"));
+
+        assert_eq!(
+            shorten("/home/user/project/src/main.rs"),
+            ("", "src/main.rs")
+        );
+        assert_eq!(
+            shorten("/home/user/other/src/main.rs"),
+            ("~/", "other/src/main.rs")
+        );
+
+        assert_eq!(shorten("src/main.rs"), ("", "src/main.rs"));
+    }
+}
@@ -0,0 +1,87 @@
+/// Constructs [`Terminator`](crate::Terminator) from message
+///
+/// Accepts either single displayable expression or format string with arguments.
+///
+/// ```
+/// fn open(name: &str) -> Result<(), terminator::Terminator> {
+///     Err(terminator::terminator!("file {name} is missing"))
+/// }
+/// assert!(open("config.toml").is_err());
+/// ```
+#[macro_export]
+macro_rules! terminator {
+    ($msg:literal $(,)?) => {
+        $crate::Terminator::msg(::std::format!($msg))
+    };
+    ($msg:expr $(,)?) => {
+        $crate::Terminator::msg($msg)
+    };
+    ($fmt:expr, $($arg:tt)*) => {
+        $crate::Terminator::msg(::std::format!($fmt, $($arg)*))
+    };
+}
+
+/// Returns early with [`Terminator`](crate::Terminator) constructed from message
+///
+/// Arguments are the same as for [`terminator!`].
+///
+/// ```
+/// fn open(name: &str) -> Result<(), terminator::Terminator> {
+///     terminator::bail!("file {name} is missing");
+/// }
+/// assert!(open("config.toml").is_err());
+/// ```
+#[macro_export]
+macro_rules! bail {
+    ($($arg:tt)+) => {
+        return ::std::result::Result::Err($crate::terminator!($($arg)+))
+    };
+}
+
+/// Returns early with [`Terminator`](crate::Terminator) if condition is not satisfied
+///
+/// Without message, the condition itself is used in the error message. Otherwise
+/// arguments after condition are the same as for [`terminator!`].
+///
+/// ```
+/// fn check(count: usize) -> Result<(), terminator::Terminator> {
+///     terminator::ensure!(count > 0);
+///     terminator::ensure!(count < 10, "too many items: {count}");
+///     Ok(())
+/// }
+/// assert!(check(0).is_err());
+/// assert!(check(5).is_ok());
+/// ```
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            $crate::bail!(::std::concat!("Condition failed: `", ::std::stringify!($cond), "`"));
+        }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            $crate::bail!($($arg)+);
+        }
+    };
+}
+
+/// Constructs [`Metadata`](crate::Metadata) from `CARGO_PKG_*` variables of calling crate
+///
+/// Metadata is marked as release build when `debug_assertions` are disabled.
+///
+/// ```
+/// let config = terminator::Config::new().metadata(terminator::metadata!());
+/// ```
+#[macro_export]
+macro_rules! metadata {
+    () => {
+        $crate::Metadata::new(
+            ::std::env!("CARGO_PKG_NAME"),
+            ::std::env!("CARGO_PKG_VERSION"),
+        )
+        .authors(::std::env!("CARGO_PKG_AUTHORS"))
+        .homepage(::std::env!("CARGO_PKG_HOMEPAGE"))
+        .release(!::std::cfg!(debug_assertions))
+    };
+}
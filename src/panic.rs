@@ -2,14 +2,19 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result;
 use std::fmt::Write as _;
+use std::io;
 use std::io::stderr;
+use std::io::IsTerminal;
 use std::io::LineWriter;
-use std::io::Write;
 use std::panic::PanicHookInfo;
 
+use crate::backtrace::Capture;
 use crate::config::Bundle;
 use crate::indent::Indent;
+use crate::link;
 use crate::location::Location;
+use crate::snippet::Snippet;
+use crate::snippet::Sources;
 use crate::Config;
 use crate::GLOBAL_SETTINGS;
 
@@ -18,7 +23,28 @@ impl Config {
     ///
     /// This can be used as panic hook only when `&self` is `'static`
     pub fn panic_hook(&self) -> impl Fn(&PanicHookInfo<'_>) + Sync + Send + '_ {
-        |info| _ = write!(LineWriter::new(stderr()), "{}", self.bundle(info))
+        |info| {
+            _ = link::destination(stderr().is_terminal(), || {
+                self.write_panic(info, &mut LineWriter::new(stderr()))
+            });
+        }
+    }
+
+    /// Writes report of panic into `writer` the same way as [`Config::panic_hook`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing into `writer` fails.
+    pub fn write_panic(
+        &self,
+        info: &PanicHookInfo<'_>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let mut report = String::new();
+        if self.friendly_panic(&mut report, info).is_none() {
+            _ = self.emit(&mut report, "panic", self.bundle(info));
+        }
+        writer.write_all(report.as_bytes())
     }
 
     /// Panic hook which lazily retrieves [`Config`] from global settings.
@@ -27,28 +53,70 @@ impl Config {
     }
 }
 
-impl Display for Bundle<'_, &PanicHookInfo<'_>> {
+pub(crate) fn payload<'a>(info: &'a PanicHookInfo<'_>) -> &'a str {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or("<non string panic payload>")
+}
+
+/// Message and location of panic, regardless of where they were obtained
+pub(crate) struct Panic<'a> {
+    pub message: &'a str,
+    pub location: Option<Location<'a>>,
+}
+
+impl Display for Bundle<'_, &Panic<'_>> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let config = self.config;
-        let payload = self.data.payload();
-        let payload = payload
-            .downcast_ref::<String>()
-            .map(String::as_str)
-            .or_else(|| payload.downcast_ref::<&str>().copied())
-            .unwrap_or("<non string panic payload>");
-
-        let theme = &config.theme;
+        let theme = &self.config.theme;
         let info = theme.header.style("The application panicked (crashed).");
-        let message = theme.message.style(payload);
-        let location = self.data.location().map(Location::derived);
-        let location = config.bundle(location.as_ref());
+        let message = theme.message.style(self.data.message);
+        let location = self.config.bundle(self.data.location.as_ref());
 
         writeln!(f, "{info}")?;
-        writeln!(f, "Message:  {message}")?;
-        writeln!(f, "Location: {location}")?;
+        write!(f, "Message:  ")?;
+        writeln!(Indent::hanging(f, "          "), "{message}")?;
+        write!(f, "Location: {location}")?;
+        if let Some(location) = self.data.location.as_ref() {
+            if self.config.show_snippets() {
+                let snippet = Snippet {
+                    location,
+                    sources: &Sources::default(),
+                };
+                write!(f, "{}", self.config.bundle(snippet))?;
+            }
+        }
+        writeln!(f)
+    }
+}
+
+impl Display for Bundle<'_, &PanicHookInfo<'_>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let config = self.config;
+
+        #[cfg(feature = "json")]
+        if config.format == crate::Format::Json {
+            return self.json(f);
+        }
+
+        let panic = Panic {
+            message: payload(self.data),
+            location: self.data.location().map(Location::derived),
+        };
+        write!(f, "{}", config.bundle(&panic))?;
+
+        #[cfg(feature = "tracing")]
+        {
+            let spantrace = tracing_error::SpanTrace::capture();
+            if spantrace.status() == tracing_error::SpanTraceStatus::CAPTURED {
+                write!(Indent::double(f), "\n{}\n", config.bundle(&spantrace))?;
+            }
+        }
 
-        let backtrace = std::backtrace::Backtrace::force_capture();
-        write!(Indent::double(f), "\n{}", config.bundle(&backtrace))?;
+        let backtrace = Capture::new();
+        write!(Indent::double(f), "\n{}", config.bundle(backtrace.frames()))?;
         writeln!(f)
     }
 }
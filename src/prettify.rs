@@ -0,0 +1,306 @@
+use std::fmt::Write as _;
+use std::io;
+
+use crate::backtrace::parse_frame;
+use crate::backtrace::parse_location;
+use crate::indent::Indent;
+use crate::link;
+use crate::location::Location;
+use crate::panic::Panic;
+use crate::Config;
+use crate::ParsedBacktrace;
+
+/// Maximum number of lines held back as possible continuation of panic message
+const CONTINUATION: usize = 32;
+
+/// Panic read from text, waiting for its message and backtrace to end
+struct Pending {
+    message: Option<String>,
+    location: Option<Location<'static>>,
+    /// Lines following the message, which are part of it only when the panic
+    /// continues with a note or backtrace
+    continuation: Vec<String>,
+}
+
+impl Pending {
+    /// Accepts held back lines as part of the message
+    fn accept(&mut self) {
+        let message = self.message.get_or_insert_with(String::new);
+        for line in self.continuation.drain(..) {
+            message.push('\n');
+            message.push_str(&line);
+        }
+    }
+}
+
+/// Rewrites panics and backtraces in output of Rust programs
+///
+/// Text is fed line by line. Panics and backtraces are held back until they
+/// end and then rendered with [`Config`]. Every other line is written out
+/// unchanged right away.
+pub struct Prettifier<'a> {
+    config: &'a Config,
+    terminal: bool,
+    panic: Option<Pending>,
+    backtrace: Option<Vec<String>>,
+}
+
+impl<'a> Prettifier<'a> {
+    /// Creates [`Prettifier`] rendering with provided [`Config`]
+    #[must_use]
+    pub fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+            terminal: false,
+            panic: None,
+            backtrace: None,
+        }
+    }
+
+    /// Allows hyperlinks in rendered reports, as output is a `terminal`
+    #[must_use]
+    pub fn terminal(mut self, terminal: bool) -> Self {
+        self.terminal = terminal;
+        self
+    }
+
+    /// Processes single line of text, without line terminator
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing into `output` fails.
+    pub fn line(&mut self, line: &str, output: &mut dyn io::Write) -> io::Result<()> {
+        let trimmed = line.trim();
+
+        if let Some(backtrace) = &mut self.backtrace {
+            if continues_backtrace(line) {
+                backtrace.push(line.to_owned());
+                return Ok(());
+            }
+            if trimmed.starts_with("note: Some details are omitted") {
+                backtrace.push(line.to_owned());
+                return self.flush(output);
+            }
+            self.flush(output)?;
+        } else if let Some(panic) = &mut self.panic {
+            // NOTE: default panic hook prints message, note and backtrace at once,
+            // but the note is printed only for the first panic
+            if header_of_backtrace(trimmed)
+                || panic.message.is_some()
+                    && parse_frame(trimmed).is_some_and(|frame| frame.index() == 0)
+            {
+                panic.accept();
+                self.backtrace = Some(vec![line.to_owned()]);
+                return Ok(());
+            }
+            if trimmed.starts_with("note: ") {
+                panic.accept();
+            } else if panic.message.is_none() {
+                panic.message = Some(line.to_owned());
+                return Ok(());
+            } else if !trimmed.is_empty()
+                && header(line).is_none()
+                && panic.continuation.len() < CONTINUATION
+            {
+                panic.continuation.push(line.to_owned());
+                return Ok(());
+            }
+            self.flush(output)?;
+        }
+
+        if let Some((message, location)) = header(line) {
+            self.panic = Some(Pending {
+                message: message.map(str::to_owned),
+                location: location.map(Location::into_owned),
+                continuation: Vec::new(),
+            });
+            return Ok(());
+        }
+        // NOTE: frames are recognized on their own only after panic, as ordinary
+        // output may look like them too
+        if header_of_backtrace(trimmed) {
+            self.backtrace = Some(vec![line.to_owned()]);
+            return Ok(());
+        }
+        writeln!(output, "{line}")
+    }
+
+    /// Renders panic or backtrace which was still being read
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing into `output` fails.
+    pub fn finish(&mut self, output: &mut dyn io::Write) -> io::Result<()> {
+        self.flush(output)
+    }
+
+    fn flush(&mut self, output: &mut dyn io::Write) -> io::Result<()> {
+        let panic = self.panic.take();
+        let backtrace = self.backtrace.take();
+        let rendered = link::destination(self.terminal, || self.render(panic.as_ref(), backtrace));
+        output.write_all(rendered.as_bytes())
+    }
+
+    fn render(&self, panic: Option<&Pending>, backtrace: Option<Vec<String>>) -> String {
+        let config = self.config;
+        let mut rendered = String::new();
+        if let Some(pending) = panic {
+            let panic = Panic {
+                message: pending.message.as_deref().unwrap_or_default(),
+                location: pending.location.as_ref().map(Location::borrowed),
+            };
+            _ = write!(rendered, "{}", config.bundle(&panic));
+            for line in &pending.continuation {
+                _ = writeln!(rendered, "{line}");
+            }
+        }
+        if let Some(lines) = backtrace {
+            let text = lines.join("\n");
+            if let Ok(parsed) = ParsedBacktrace::parse(&text) {
+                let frames = config.bundle(parsed.frames());
+                if panic.is_some() {
+                    _ = writeln!(Indent::double(&mut rendered), "\n{frames}");
+                } else {
+                    _ = writeln!(rendered, "{frames}");
+                }
+                // NOTE: lines which aren't part of backtrace after all are kept
+                for diagnostic in parsed.diagnostics() {
+                    _ = writeln!(rendered, "{}", diagnostic.text());
+                }
+            } else {
+                rendered.push_str(&text);
+                rendered.push('\n');
+            }
+        }
+        rendered
+    }
+}
+
+/// Parses panic header of default panic hook into message and location
+///
+/// Message is on the following lines in current format (`panicked at file:1:2:`),
+/// but on the same line in the old one (`panicked at 'message', file:1:2`).
+fn header(line: &str) -> Option<(Option<&str>, Option<Location<'_>>)> {
+    let (_, rest) = line.strip_prefix("thread '")?.split_once(" panicked at ")?;
+    if let Some(location) = rest.strip_suffix(':') {
+        return Some((None, parse_location(location)));
+    }
+    let (message, location) = rest.rsplit_once(", ")?;
+    let message = message.strip_prefix('\'')?.strip_suffix('\'')?;
+    Some((Some(message), parse_location(location)))
+}
+
+/// Checks whether line is header of backtrace printed by standard library,
+/// which is capitalized in `Debug` output of `anyhow`
+fn header_of_backtrace(trimmed: &str) -> bool {
+    trimmed.eq_ignore_ascii_case("stack backtrace:")
+}
+
+/// Checks whether line can be part of backtrace which already started
+fn continues_backtrace(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && (parse_frame(trimmed).is_some()
+            || trimmed.starts_with("at ")
+            || line.starts_with("      "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Theme;
+    use crate::Verbosity;
+
+    fn prettify(text: &str) -> String {
+        let config = Config::blank()
+            .theme(Theme::blank())
+            .verbosity(Verbosity::Full);
+        let mut prettifier = Prettifier::new(&config);
+        let mut output = Vec::new();
+        for line in text.lines() {
+            prettifier.line(line, &mut output).unwrap();
+        }
+        prettifier.finish(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn passthrough() {
+        let text = "first line\n  1: not a backtrace\n\nlast line\n";
+        assert_eq!(prettify(text), text);
+    }
+
+    #[test]
+    fn panic() {
+        let text = "\
+before
+thread 'main' (1234) panicked at src/main.rs:2:5:
+first
+second
+stack backtrace:
+   0: app::main
+             at ./src/main.rs:2:5
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.
+after
+";
+        let output = prettify(text);
+        assert!(output.starts_with(
+            "before\nThe application panicked (crashed).\nMessage:  first\n          second\nLocation: src/main.rs:2:5\n"
+        ));
+        assert!(output.contains(" BACKTRACE "));
+        assert!(output.contains("   0: app::main\n      at ./src/main.rs:2:5"));
+        assert!(output.ends_with("\nafter\n"));
+    }
+
+    #[test]
+    fn lookalike() {
+        let text = "first line\n   0: starting step\n   1: next step\nlast line\n";
+        assert_eq!(prettify(text), text);
+    }
+
+    #[test]
+    fn unparsed() {
+        let text = "\
+stack backtrace:
+   0: app::main
+      called from the scheduler
+after
+";
+        let output = prettify(text);
+        assert!(output.starts_with("━━━━"));
+        assert!(output.ends_with(
+            "\n 0: app::main\n    at <unknown>\n      called from the scheduler\nafter\n"
+        ));
+    }
+
+    #[test]
+    fn old_panic() {
+        let text = "\
+thread 'main' panicked at 'boom', src/main.rs:2:5
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+";
+        let output = prettify(text);
+        assert!(output.starts_with("The application panicked (crashed).\nMessage:  boom\n"));
+        assert!(output.ends_with(
+            "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n"
+        ));
+    }
+
+    #[test]
+    fn repeated_panic() {
+        let text = "\
+thread 'main' panicked at src/main.rs:2:5:
+first
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+thread 'worker' panicked at src/main.rs:7:9:
+second
+regular output line
+failures:
+test result: FAILED
+";
+        let output = prettify(text);
+        assert!(output.contains("Message:  first\nLocation: src/main.rs:2:5\n"));
+        assert!(output.contains("Message:  second\nLocation: src/main.rs:7:9\n"));
+        assert!(output.ends_with("\nregular output line\nfailures:\ntest result: FAILED\n"));
+    }
+}
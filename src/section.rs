@@ -0,0 +1,246 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Write;
+
+use crate::config::Bundle;
+use crate::indent::Indent;
+use crate::Terminator;
+
+type Body = Box<dyn Display + Send + Sync + 'static>;
+
+/// Free-form section attached to [`Terminator`]
+pub(crate) enum Help {
+    Note(Body),
+    Suggestion(Body),
+    Warning(Body),
+}
+
+impl Display for Bundle<'_, &Help> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = &self.config.theme;
+        let (label, style, body) = match self.data {
+            Help::Note(body) => ("Note:", theme.note, body),
+            Help::Suggestion(body) => ("Suggestion:", theme.suggestion, body),
+            Help::Warning(body) => ("Warning:", theme.warning, body),
+        };
+        write!(f, "{} ", style.style(label))?;
+        let indentation = " ".repeat(label.len() + 1);
+        write!(Indent::hanging(f, &indentation), "{body}")
+    }
+}
+
+mod sealed {
+    use crate::Terminator;
+
+    pub trait Sealed {}
+
+    impl Sealed for Terminator {}
+    impl<T, E> Sealed for Result<T, E> where E: Into<Terminator> {}
+}
+
+/// Extension trait for attaching notes, suggestions and warnings to errors
+///
+/// Sections are printed after the error chain in order they were added.
+pub trait Section: sealed::Sealed {
+    /// Type returned after attaching section
+    type Return;
+
+    /// Attach note to the error
+    fn note<D>(self, note: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static;
+
+    /// Attach lazily evaluated note to the error
+    fn with_note<D, F>(self, note: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D;
+
+    /// Attach suggestion to the error
+    fn suggestion<D>(self, suggestion: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static;
+
+    /// Attach lazily evaluated suggestion to the error
+    fn with_suggestion<D, F>(self, suggestion: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D;
+
+    /// Attach warning to the error
+    fn warning<D>(self, warning: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static;
+
+    /// Attach lazily evaluated warning to the error
+    fn with_warning<D, F>(self, warning: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D;
+}
+
+impl Section for Terminator {
+    type Return = Self;
+
+    fn note<D>(mut self, note: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        self.report.help.push(Help::Note(Box::new(note)));
+        self
+    }
+
+    fn with_note<D, F>(self, note: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        self.note(note())
+    }
+
+    fn suggestion<D>(mut self, suggestion: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        self.report
+            .help
+            .push(Help::Suggestion(Box::new(suggestion)));
+        self
+    }
+
+    fn with_suggestion<D, F>(self, suggestion: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        self.suggestion(suggestion())
+    }
+
+    fn warning<D>(mut self, warning: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        self.report.help.push(Help::Warning(Box::new(warning)));
+        self
+    }
+
+    fn with_warning<D, F>(self, warning: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        self.warning(warning())
+    }
+}
+
+impl<T, E> Section for Result<T, E>
+where
+    E: Into<Terminator>,
+{
+    type Return = Result<T, Terminator>;
+
+    #[track_caller]
+    fn note<D>(self, note: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().note(note)),
+        }
+    }
+
+    #[track_caller]
+    fn with_note<D, F>(self, note: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().with_note(note)),
+        }
+    }
+
+    #[track_caller]
+    fn suggestion<D>(self, suggestion: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().suggestion(suggestion)),
+        }
+    }
+
+    #[track_caller]
+    fn with_suggestion<D, F>(self, suggestion: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().with_suggestion(suggestion)),
+        }
+    }
+
+    #[track_caller]
+    fn warning<D>(self, warning: D) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().warning(warning)),
+        }
+    }
+
+    #[track_caller]
+    fn with_warning<D, F>(self, warning: F) -> Self::Return
+    where
+        D: Display + Send + Sync + 'static,
+        F: FnOnce() -> D,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().with_warning(warning)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::failure;
+    use crate::Config;
+    use crate::Theme;
+
+    #[test]
+    fn ordering() {
+        let error = Err::<(), _>(failure())
+            .warning("first")
+            .note("second")
+            .with_suggestion(|| "third")
+            .unwrap_err();
+        let config = Config::blank().theme(Theme::blank());
+        let rendered: Vec<_> = error
+            .report
+            .help
+            .iter()
+            .map(|help| config.bundle(help).to_string())
+            .collect();
+        assert_eq!(
+            rendered,
+            ["Warning: first", "Note: second", "Suggestion: third"]
+        );
+    }
+
+    #[test]
+    fn multiline() {
+        let error = failure().suggestion("first\nsecond");
+        let config = Config::blank().theme(Theme::blank());
+        let rendered = config.bundle(&error.report.help[0]).to_string();
+        assert_eq!(rendered, "Suggestion: first\n            second");
+    }
+}
@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use std::fs;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+
+use crate::config::Bundle;
+use crate::location::Location;
+use crate::Config;
+use crate::Verbosity;
+
+/// Number of lines shown before and after the line of interest
+const CONTEXT: usize = 2;
+
+/// Size of the largest source file which is read
+const MAX_SIZE: u64 = 1 << 22;
+
+impl Config {
+    /// Show lines of source code around panic location and frames of your crate
+    ///
+    /// Snippets are always shown with [`Verbosity::Full`]. Files which cannot
+    /// be read are skipped.
+    #[inline]
+    pub fn snippets(mut self, enabled: bool) -> Self {
+        self.snippets = enabled;
+        self
+    }
+
+    pub(crate) fn show_snippets(&self) -> bool {
+        self.snippets || self.selected_verbosity() == Verbosity::Full
+    }
+}
+
+/// Source files read during single render, so each of them is read only once
+#[derive(Default)]
+pub(crate) struct Sources {
+    files: RefCell<HashMap<String, Option<Source>>>,
+}
+
+/// Leading lines of source file
+struct Source {
+    lines: Vec<String>,
+    complete: bool,
+}
+
+impl Sources {
+    /// Gets lines `first..=last` (numbered from 1) of `file`, if it can be read
+    fn lines(&self, file: &str, first: usize, last: usize) -> Option<Vec<String>> {
+        let mut files = self.files.borrow_mut();
+        let cached = files.get(file).is_some_and(|source| {
+            source
+                .as_ref()
+                .is_none_or(|source| source.complete || source.lines.len() >= last)
+        });
+        if !cached {
+            files.insert(file.to_owned(), read(file, last));
+        }
+        let source = files.get(file)?.as_ref()?;
+        let lines = source.lines.get(first - 1..last.min(source.lines.len()))?;
+        Some(lines.to_vec())
+    }
+}
+
+/// Reads at most `count` leading lines of regular file of reasonable size
+fn read(file: &str, count: usize) -> Option<Source> {
+    let metadata = fs::metadata(file).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_SIZE {
+        return None;
+    }
+
+    let mut reader = BufReader::new(File::open(file).ok()?);
+    let mut lines = Vec::new();
+    let mut complete = false;
+    while lines.len() < count {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            complete = true;
+            break;
+        }
+        let length = line.trim_end_matches(['\n', '\r']).len();
+        line.truncate(length);
+        lines.push(line);
+    }
+    Some(Source { lines, complete })
+}
+
+/// Lines of source code surrounding [`Location`]
+pub(crate) struct Snippet<'a> {
+    pub location: &'a Location<'a>,
+    pub sources: &'a Sources,
+}
+
+impl Display for Bundle<'_, Snippet<'_>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let theme = &self.config.theme;
+        let Snippet { location, sources } = self.data;
+
+        let target = location.line() as usize;
+        let first = target.saturating_sub(CONTEXT).max(1);
+        let Some(lines) = sources
+            .lines(location.file(), first, target + CONTEXT)
+            .filter(|lines| first + lines.len() > target)
+        else {
+            return Ok(());
+        };
+
+        let width = (first + lines.len() - 1).to_string().len();
+        for (number, line) in (first..).zip(lines) {
+            let (style, marker) = if number == target {
+                (theme.active_line, '>')
+            } else {
+                (theme.code, '│')
+            };
+            let line = format!("{number:>width$} {marker} {line}");
+            write!(f, "\n{}", style.style(line))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::Theme;
+
+    #[test]
+    fn snippet() {
+        let path = env::temp_dir().join(format!("terminator-snippet-{}.rs", process::id()));
+        let source: Vec<_> = (1..=12).map(|line| format!("line {line}")).collect();
+        fs::write(&path, source.join("\n")).unwrap();
+
+        let config = Config::blank().theme(Theme::blank());
+        let render = |file: &str, line, sources: &Sources| {
+            let location = Location {
+                file: Cow::Borrowed(file),
+                line,
+                column: None,
+            };
+            let snippet = Snippet {
+                location: &location,
+                sources,
+            };
+            config.bundle(snippet).to_string()
+        };
+        let file = path.display().to_string();
+        let sources = Sources::default();
+
+        let expected = "\n 8 │ line 8\n 9 │ line 9\n10 > line 10\n11 │ line 11\n12 │ line 12";
+        assert_eq!(render(&file, 10, &sources), expected);
+        assert_eq!(
+            render(&file, 1, &sources),
+            "\n1 > line 1\n2 │ line 2\n3 │ line 3"
+        );
+        assert_eq!(render(&file, 13, &sources), "");
+
+        // NOTE: file is read only once per render
+        fs::remove_file(&path).unwrap();
+        assert_eq!(render(&file, 10, &sources), expected);
+        assert_eq!(render(&file, 10, &Sources::default()), "");
+
+        let sources = Sources::default();
+        let directory = env::temp_dir().display().to_string();
+        assert_eq!(render(&directory, 1, &sources), "");
+        #[cfg(unix)]
+        assert_eq!(render("/dev/zero", 1, &sources), "");
+    }
+}
@@ -195,6 +195,11 @@ impl Style {
         self
     }
 
+    /// Checks whether style leaves text unaltered
+    pub(crate) fn is_blank(self) -> bool {
+        self.colors.fg.is_none() && self.colors.bg.is_none() && self.effects.bytes == 0
+    }
+
     pub(crate) fn style<T: fmt::Display>(self, thing: T) -> Styled<T> {
         Styled { style: self, thing }
     }
@@ -212,7 +217,7 @@ impl<T: fmt::Display> fmt::Display for Styled<T> {
             thing,
         } = self;
 
-        let meta = colors.bg.is_some() || colors.fg.is_some() || effects.bytes != 0;
+        let meta = !self.style.is_blank();
 
         if meta {
             f.write_str("\x1b[")?;
@@ -254,6 +259,11 @@ macro_rules! theme {
         }
 
         impl Theme {
+            /// Checks whether no style of theme alters text
+            pub(crate) fn is_blank(&self) -> bool {
+                true $(&& self.$name.is_blank())*
+            }
+
             $(
             #[$meta]
             #[inline]
@@ -285,6 +295,22 @@ theme! {
     message,
     /// Styles the "N frames hidden" message
     hidden,
+    /// Styles the label of notes attached to errors
+    note,
+    /// Styles the label of suggestions attached to errors
+    suggestion,
+    /// Styles the label of warnings attached to errors
+    warning,
+    /// Styles the name of span in span trace
+    span,
+    /// Styles the recorded fields of span in span trace
+    fields,
+    /// Styles lines of source code around the line of interest
+    code,
+    /// Styles the line of source code pointed to by location
+    active_line,
+    /// Styles links to source code of frames on the web
+    url,
 }
 
 impl Theme {
@@ -301,6 +327,14 @@ impl Theme {
             header: Style::new(),
             message: Style::new(),
             hidden: Style::new(),
+            note: Style::new(),
+            suggestion: Style::new(),
+            warning: Style::new(),
+            span: Style::new(),
+            fields: Style::new(),
+            code: Style::new(),
+            active_line: Style::new(),
+            url: Style::new(),
         }
     }
 
@@ -323,6 +357,14 @@ impl Theme {
             header: Style::new().fg(Color::Red),
             message: Style::new().fg(Color::Cyan),
             hidden: Style::new().fg(Color::BrightCyan),
+            note: Style::new().fg(Color::Cyan),
+            suggestion: Style::new().fg(Color::Cyan),
+            warning: Style::new().fg(Color::BrightYellow),
+            span: Style::new().fg(Color::BrightRed),
+            fields: Style::new().fg(Color::BrightCyan),
+            code: Style::new().fg(Color::BrightBlack),
+            active_line: Style::new().fg(Color::White).set(Effect::Bold),
+            url: Style::new().fg(Color::BrightBlack).set(Effect::Dimmed),
         }
     }
 
@@ -339,6 +381,14 @@ impl Theme {
             header: Style::new().fg(Color::Red),
             message: Style::new().fg(Color::Blue),
             hidden: Style::new().fg(Color::Blue),
+            note: Style::new().fg(Color::Blue),
+            suggestion: Style::new().fg(Color::Blue),
+            warning: Style::new().fg(Color::Yellow),
+            span: Style::new().fg(Color::Red),
+            fields: Style::new().fg(Color::Blue),
+            code: Style::new().fg(Color::BrightBlack),
+            active_line: Style::new().fg(Color::Black).set(Effect::Bold),
+            url: Style::new().fg(Color::BrightBlack).set(Effect::Dimmed),
         }
     }
 }
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::fmt::Display;
+use std::panic;
+
+use crate::config::Bundle;
+use crate::location::Location;
+use crate::Terminator;
+
+/// Locations through which error was propagated, innermost first
+#[derive(Debug, Default)]
+pub(crate) struct ReturnTrace(pub Vec<&'static panic::Location<'static>>);
+
+impl Display for Bundle<'_, &ReturnTrace> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let config = self.config;
+        write!(f, "{:━^80}", " RETURN TRACE ")?;
+        for (index, location) in self.data.0.iter().enumerate() {
+            let location = Location::derived(location);
+            write!(f, "\n{index:>4}: {}", config.bundle(&location))?;
+        }
+        Ok(())
+    }
+}
+
+mod sealed {
+    use crate::Terminator;
+
+    pub trait Sealed {}
+
+    impl<T, E> Sealed for Result<T, E> where E: Into<Terminator> {}
+}
+
+/// Extension trait for recording error return traces
+///
+/// Each call appends location of the caller to the return trace of the error,
+/// which is shown after the error chain. Use it right before propagating error
+/// with `?` to get the path the error took back up the stack.
+///
+/// ```
+/// use terminator::Propagate;
+///
+/// fn inner() -> Result<(), terminator::Terminator> {
+///     terminator::bail!("failure");
+/// }
+///
+/// fn outer() -> Result<(), terminator::Terminator> {
+///     inner().propagate()?;
+///     Ok(())
+/// }
+///
+/// let error = outer().propagate().unwrap_err();
+/// assert_eq!(error.return_trace().len(), 2);
+/// ```
+pub trait Propagate<T>: sealed::Sealed {
+    /// Record location of the caller in return trace of the error
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Terminator`] with extended return trace if `self` holds an error.
+    fn propagate(self) -> Result<T, Terminator>;
+}
+
+impl<T, E> Propagate<T> for Result<T, E>
+where
+    E: Into<Terminator>,
+{
+    #[track_caller]
+    fn propagate(self) -> Result<T, Terminator> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => {
+                let mut error = error.into();
+                error.report.trace.0.push(panic::Location::caller());
+                Err(error)
+            }
+        }
+    }
+}
+
+impl Terminator {
+    /// Locations recorded with [`Propagate`], innermost first
+    #[must_use]
+    pub fn return_trace(&self) -> &[&'static panic::Location<'static>] {
+        &self.report.trace.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use crate::Theme;
+
+    /// Propagates `result`, returning also location of the caller
+    #[track_caller]
+    fn propagate(
+        result: Result<(), Terminator>,
+    ) -> (Result<(), Terminator>, &'static panic::Location<'static>) {
+        (result.propagate(), panic::Location::caller())
+    }
+
+    #[test]
+    fn rendering() {
+        let (inner, inner_location) = propagate(Err(Terminator::msg("failure")));
+        let (outer, outer_location) = propagate(inner);
+        let error = outer.unwrap_err();
+
+        let config = Config::blank().theme(Theme::blank());
+        let rendered = config.bundle(&error.report.trace).to_string();
+        let lines: Vec<_> = rendered.lines().skip(1).collect();
+        let expected = |location: &panic::Location| {
+            format!("{}:{}:{}", file!(), location.line(), location.column())
+        };
+        assert_eq!(
+            lines,
+            [
+                format!("   0: {}", expected(inner_location)),
+                format!("   1: {}", expected(outer_location)),
+            ]
+        );
+    }
+}
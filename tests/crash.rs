@@ -0,0 +1,26 @@
+//! Crash reports are saved only when reporting errors, not when formatting them
+//!
+//! Separate from unit tests, as it installs [`Config`] globally.
+#![allow(missing_docs)]
+
+use std::fs;
+use std::process;
+use std::process::Termination;
+
+use terminator::Config;
+use terminator::Outcome;
+use terminator::Terminator;
+
+#[test]
+fn debug() {
+    let dir = std::env::temp_dir().join(format!("terminator-debug-{}", process::id()));
+    Config::new().crash_dir(&dir).install().unwrap();
+
+    let error = Terminator::msg("message");
+    assert!(format!("{error:?}").contains("message"));
+    assert!(!dir.exists());
+
+    _ = Outcome::<()>(Err(error)).report();
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,54 @@
+//! Writing panic reports into arbitrary writers
+//!
+//! Separate from unit tests, as it replaces panic hook of the process.
+#![allow(missing_docs)]
+
+use std::fs;
+use std::panic;
+use std::process;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use terminator::Config;
+use terminator::Theme;
+
+/// Panics with `message` and returns report written by `config`
+fn report(config: Config, message: &'static str) -> String {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let writer = Arc::clone(&output);
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        config
+            .write_panic(info, &mut *writer.lock().unwrap())
+            .unwrap();
+    }));
+    _ = panic::catch_unwind(|| panic!("{message}"));
+    panic::set_hook(hook);
+
+    let output = output.lock().unwrap();
+    String::from_utf8(output.clone()).unwrap()
+}
+
+#[test]
+fn write_panic() {
+    let config = Config::blank().theme(Theme::blank());
+    let output = report(config, "first");
+    assert!(output.starts_with("The application panicked (crashed).\nMessage:  first\n"));
+    assert!(output.contains(&format!("Location: {}:", file!())));
+
+    // NOTE: destination of the report is unknown, so no hyperlinks are written
+    let config = Config::blank().links("file://{path}");
+    let output = report(config, "second");
+    assert!(output.contains("second"));
+    assert!(!output.contains("\x1b]8;;"));
+
+    let dir = std::env::temp_dir().join(format!("terminator-panic-{}", process::id()));
+    let config = Config::blank().theme(Theme::blank()).crash_dir(&dir);
+    let output = report(config, "third");
+    let path = fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("Message:  third\n"));
+    assert!(output.contains(&format!("The report was saved to: {}", path.display())));
+    fs::remove_dir_all(dir).unwrap();
+}